@@ -0,0 +1,440 @@
+use chrono::{Local, NaiveDate};
+use clap::{Parser, Subcommand};
+
+use std::path::PathBuf;
+
+use crate::{
+    db::Backend,
+    export::ExportFormat,
+    models::{parse_due_date, Priority},
+};
+
+// Relative due dates like `+3d` count from today.
+fn due_date(input: &str) -> Result<NaiveDate, String> {
+    parse_due_date(input, Local::now().date_naive()).map_err(|error| error.to_string())
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "my-jira",
+    version,
+    about = "A minimal Jira clone for the terminal"
+)]
+pub struct Cli {
+    /// Recover the board from the newest readable backup if the database file is corrupted
+    #[arg(long)]
+    pub restore_backup: bool,
+
+    /// Storage backend used for the board (guessed from the file extension by default)
+    #[arg(long, value_enum, env = "MY_JIRA_BACKEND")]
+    pub backend: Option<Backend>,
+
+    /// Path of the board file to open
+    #[arg(long = "db", env = "MY_JIRA_DB")]
+    pub db_path: Option<String>,
+
+    /// Name of a board listed in the config file
+    #[arg(long)]
+    pub board: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Command {
+    /// Start an empty board at the selected path
+    Init,
+    /// Upgrade the board file to the latest schema version, keeping a backup of the old file
+    Migrate,
+    /// Check the board for broken references between epics and stories
+    Fsck {
+        /// Fix every problem found instead of only reporting it
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Create, list, update and delete epics
+    #[command(subcommand)]
+    Epic(EpicCommand),
+    /// Create, list, update and delete stories
+    #[command(subcommand)]
+    Story(StoryCommand),
+    /// Manage the team members stories can be assigned to
+    #[command(subcommand)]
+    Person(PersonCommand),
+    /// List, rename and merge the labels of the board
+    #[command(subcommand)]
+    Label(LabelCommand),
+    /// Render the epics with their stories and progress as a report
+    Export {
+        #[arg(long, value_enum, default_value = "md")]
+        format: ExportFormat,
+        /// Only include these epics
+        #[arg(long = "epic")]
+        epic_ids: Vec<u32>,
+        /// Only include stories in these states, and epics that are in one or have such stories
+        #[arg(long = "status")]
+        statuses: Vec<String>,
+        /// Write the report to this file instead of printing it
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Plan, start and close sprints
+    #[command(subcommand)]
+    Sprint(SprintCommand),
+    /// Show the history of changes made to the board, oldest first
+    Log {
+        /// Only show changes to this epic and its stories
+        #[arg(long = "epic", conflicts_with = "story_id")]
+        epic_id: Option<u32>,
+        /// Only show changes to this story
+        #[arg(long = "story")]
+        story_id: Option<u32>,
+        /// Only show the most recent changes
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Print the changes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum EpicCommand {
+    /// Create an epic and print its id
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long = "desc", default_value = "")]
+        description: String,
+        /// Due date, as YYYY-MM-DD or +Nd for N days from today
+        #[arg(long, value_parser = due_date)]
+        due: Option<NaiveDate>,
+    },
+    /// List every epic
+    List {
+        /// Print the epics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move an epic to another state of the board's workflow
+    Status {
+        epic_id: u32,
+        /// Name of the state, like in-progress (case and separators are ignored)
+        status: String,
+    },
+    /// Assign an epic to a registered team member
+    Assign { epic_id: u32, username: String },
+    /// Remove the assignee of an epic
+    Unassign { epic_id: u32 },
+    /// Set the due date of an epic, as YYYY-MM-DD or +Nd, left out to clear it
+    Due {
+        epic_id: u32,
+        #[arg(value_parser = due_date)]
+        due: Option<NaiveDate>,
+    },
+    /// Add a label to an epic, creating the label if needed
+    Label { epic_id: u32, label: String },
+    /// Remove a label from an epic
+    Unlabel { epic_id: u32, label: String },
+    /// Delete an epic and all of its stories
+    Delete { epic_id: u32 },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum StoryCommand {
+    /// Create a story in an epic and print its id
+    Create {
+        #[arg(long = "epic")]
+        epic_id: u32,
+        #[arg(long)]
+        name: String,
+        #[arg(long = "desc", default_value = "")]
+        description: String,
+        #[arg(long, value_enum, default_value = "medium")]
+        priority: Priority,
+        /// Story point estimate
+        #[arg(long)]
+        points: Option<u32>,
+        /// Due date, as YYYY-MM-DD or +Nd for N days from today
+        #[arg(long, value_parser = due_date)]
+        due: Option<NaiveDate>,
+    },
+    /// List the stories of an epic
+    List {
+        #[arg(long = "epic")]
+        epic_id: u32,
+        /// Print the stories as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move a story to another state of the board's workflow
+    Status {
+        story_id: u32,
+        /// Name of the state, like in-progress (case and separators are ignored)
+        status: String,
+    },
+    /// Set the priority and story point estimate of a story
+    Estimate {
+        story_id: u32,
+        #[arg(long, value_enum)]
+        priority: Priority,
+        /// Story point estimate, left out to clear it
+        #[arg(long)]
+        points: Option<u32>,
+    },
+    /// Assign a story to a registered team member
+    Assign { story_id: u32, username: String },
+    /// Remove the assignee of a story
+    Unassign { story_id: u32 },
+    /// Set the due date of a story, as YYYY-MM-DD or +Nd, left out to clear it
+    Due {
+        story_id: u32,
+        #[arg(value_parser = due_date)]
+        due: Option<NaiveDate>,
+    },
+    /// Mark a story as blocked by another story, from any epic
+    Block {
+        story_id: u32,
+        #[arg(long = "by")]
+        blocker_id: u32,
+    },
+    /// Remove a dependency between two stories
+    Unblock {
+        story_id: u32,
+        #[arg(long = "by")]
+        blocker_id: u32,
+    },
+    /// Add a label to a story, creating the label if needed
+    Label { story_id: u32, label: String },
+    /// Remove a label from a story
+    Unlabel { story_id: u32, label: String },
+    /// Delete a story from an epic
+    Delete {
+        #[arg(long = "epic")]
+        epic_id: u32,
+        story_id: u32,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum PersonCommand {
+    /// Register a team member
+    Add {
+        username: String,
+        /// Full name of the team member
+        #[arg(long)]
+        name: String,
+    },
+    /// List the registered team members
+    List {
+        /// Print the team members as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Unassign a team member from everything and remove them
+    Remove { username: String },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum LabelCommand {
+    /// List the labels and how many epics and stories use them
+    List {
+        /// Print the labels as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rename a label everywhere it is used
+    Rename { label: String, new_name: String },
+    /// Move everything labelled FROM over to INTO and delete FROM
+    Merge { from: String, into: String },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum SprintCommand {
+    /// Start a sprint with the stories the previous one left unfinished
+    Start {
+        #[arg(long)]
+        name: String,
+        /// First day of the sprint, today if not given
+        #[arg(long, value_parser = due_date)]
+        start: Option<NaiveDate>,
+        /// Last day of the sprint, two weeks after it starts if not given
+        #[arg(long, value_parser = due_date)]
+        end: Option<NaiveDate>,
+    },
+    /// Close the active sprint
+    Close,
+    /// Add a story to the active sprint
+    Add { story_id: u32 },
+    /// Remove a story from the active sprint
+    Remove { story_id: u32 },
+    /// Show the active sprint and its stories
+    Show {
+        /// Print the sprint as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Draw the burndown of the active sprint
+    Burndown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Option<Command> {
+        Cli::try_parse_from(std::iter::once("my-jira").chain(args.iter().copied()))
+            .unwrap()
+            .command
+    }
+
+    #[test]
+    fn cli_should_parse_scripting_subcommands() {
+        assert_eq!(parse(&["init"]), Some(Command::Init));
+        assert_eq!(
+            parse(&["epic", "create", "--name", "Epic", "--desc", "About"]),
+            Some(Command::Epic(EpicCommand::Create {
+                name: "Epic".to_owned(),
+                description: "About".to_owned(),
+                due: None
+            }))
+        );
+        assert_eq!(
+            parse(&["story", "status", "7", "resolved"]),
+            Some(Command::Story(StoryCommand::Status {
+                story_id: 7,
+                status: "resolved".to_owned()
+            }))
+        );
+        assert_eq!(
+            parse(&["story", "status", "7", "in-progress"]),
+            Some(Command::Story(StoryCommand::Status {
+                story_id: 7,
+                status: "in-progress".to_owned()
+            }))
+        );
+        assert_eq!(
+            parse(&["story", "delete", "--epic", "1", "7"]),
+            Some(Command::Story(StoryCommand::Delete {
+                epic_id: 1,
+                story_id: 7
+            }))
+        );
+        assert_eq!(
+            parse(&["epic", "list", "--json"]),
+            Some(Command::Epic(EpicCommand::List { json: true }))
+        );
+        assert_eq!(
+            parse(&["label", "merge", "Backend", "api"]),
+            Some(Command::Label(LabelCommand::Merge {
+                from: "Backend".to_owned(),
+                into: "api".to_owned()
+            }))
+        );
+    }
+
+    #[test]
+    fn cli_should_parse_log_filters() {
+        assert_eq!(
+            parse(&["log", "--epic", "1", "--limit", "5"]),
+            Some(Command::Log {
+                epic_id: Some(1),
+                story_id: None,
+                limit: Some(5),
+                json: false
+            })
+        );
+        assert!(Cli::try_parse_from(["my-jira", "log", "--epic", "1", "--story", "2"]).is_err());
+    }
+
+    #[test]
+    fn cli_should_parse_dependencies() {
+        assert_eq!(
+            parse(&["story", "block", "7", "--by", "3"]),
+            Some(Command::Story(StoryCommand::Block {
+                story_id: 7,
+                blocker_id: 3
+            }))
+        );
+        assert!(Cli::try_parse_from(["my-jira", "story", "unblock", "7"]).is_err());
+    }
+
+    #[test]
+    fn cli_should_parse_sprints() {
+        assert_eq!(
+            parse(&[
+                "sprint",
+                "start",
+                "--name",
+                "Sprint 1",
+                "--end",
+                "2026-11-13"
+            ]),
+            Some(Command::Sprint(SprintCommand::Start {
+                name: "Sprint 1".to_owned(),
+                start: None,
+                end: NaiveDate::from_ymd_opt(2026, 11, 13)
+            }))
+        );
+        assert_eq!(
+            parse(&["sprint", "add", "7"]),
+            Some(Command::Sprint(SprintCommand::Add { story_id: 7 }))
+        );
+        assert!(Cli::try_parse_from(["my-jira", "sprint", "start"]).is_err());
+    }
+
+    #[test]
+    fn cli_should_parse_exports() {
+        assert_eq!(
+            parse(&[
+                "export",
+                "--format",
+                "csv",
+                "--epic",
+                "1",
+                "--epic",
+                "4",
+                "--status",
+                "open",
+                "-o",
+                "report.csv"
+            ]),
+            Some(Command::Export {
+                format: ExportFormat::Csv,
+                epic_ids: vec![1, 4],
+                statuses: vec!["open".to_owned()],
+                output: Some(PathBuf::from("report.csv"))
+            })
+        );
+        assert_eq!(
+            parse(&["export"]),
+            Some(Command::Export {
+                format: ExportFormat::Md,
+                epic_ids: vec![],
+                statuses: vec![],
+                output: None
+            })
+        );
+        assert!(Cli::try_parse_from(["my-jira", "export", "--format", "pdf"]).is_err());
+    }
+
+    #[test]
+    fn cli_should_parse_due_dates() {
+        assert_eq!(
+            parse(&["story", "due", "7", "2026-11-01"]),
+            Some(Command::Story(StoryCommand::Due {
+                story_id: 7,
+                due: NaiveDate::from_ymd_opt(2026, 11, 1)
+            }))
+        );
+        assert_eq!(
+            parse(&["epic", "due", "1"]),
+            Some(Command::Epic(EpicCommand::Due {
+                epic_id: 1,
+                due: None
+            }))
+        );
+        assert!(Cli::try_parse_from(["my-jira", "epic", "due", "1", "soon"]).is_err());
+    }
+}
@@ -0,0 +1,828 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{Days, Local};
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    cli::{EpicCommand, LabelCommand, PersonCommand, SprintCommand, StoryCommand},
+    db::JiraDatabase,
+    export::{export, ExportFilter, ExportFormat},
+    models::{Epic, ItemKind, Person, Story},
+};
+
+// Scripted counterparts of the navigator's actions. They call the same `JiraDatabase`
+// methods, only without prompting: every answer comes from the command line instead.
+
+#[derive(Serialize)]
+struct Listed<'a, T> {
+    id: u32,
+    #[serde(flatten)]
+    item: &'a T,
+}
+
+pub fn run_epic_command(db: &JiraDatabase, command: EpicCommand) -> Result<()> {
+    match command {
+        EpicCommand::Create {
+            name,
+            description,
+            due,
+        } => {
+            let epic = Epic {
+                due,
+                ..Epic::new(name, description)
+            };
+            let epic_id = db
+                .create_epic(epic)
+                .with_context(|| anyhow!("failed to create epic!"))?;
+            println!("{}", epic_id);
+        }
+        EpicCommand::List { json } => {
+            let epics = db.get_epics()?;
+            let epics = epics
+                .iter()
+                .sorted_by_key(|(id, _)| **id)
+                .map(|(id, epic)| Listed {
+                    id: *id,
+                    item: epic,
+                })
+                .collect::<Vec<_>>();
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&epics)?);
+            } else {
+                for Listed { id, item: epic } in epics {
+                    println!("{}\t{}\t{}", id, epic.status, epic.name);
+                }
+            }
+        }
+        EpicCommand::Status { epic_id, status } => {
+            let status = db.get_workflow()?.find_state(&status)?;
+            db.update_epic_status(epic_id, status)
+                .with_context(|| anyhow!("failed to update epic!"))?;
+        }
+        EpicCommand::Assign { epic_id, username } => {
+            db.assign_epic(epic_id, Some(username), None)
+                .with_context(|| anyhow!("failed to assign epic!"))?;
+        }
+        EpicCommand::Unassign { epic_id } => {
+            db.assign_epic(epic_id, None, None)
+                .with_context(|| anyhow!("failed to unassign epic!"))?;
+        }
+        EpicCommand::Due { epic_id, due } => {
+            db.set_epic_due(epic_id, due)
+                .with_context(|| anyhow!("failed to update epic!"))?;
+        }
+        EpicCommand::Label { epic_id, label } => {
+            db.add_epic_label(epic_id, &label)
+                .with_context(|| anyhow!("failed to label epic!"))?;
+        }
+        EpicCommand::Unlabel { epic_id, label } => {
+            db.remove_epic_label(epic_id, &label)
+                .with_context(|| anyhow!("failed to unlabel epic!"))?;
+        }
+        EpicCommand::Delete { epic_id } => {
+            db.delete_epic(epic_id)
+                .with_context(|| anyhow!("failed to delete epic!"))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run_story_command(db: &JiraDatabase, command: StoryCommand) -> Result<()> {
+    match command {
+        StoryCommand::Create {
+            epic_id,
+            name,
+            description,
+            priority,
+            points,
+            due,
+        } => {
+            let story = Story {
+                priority,
+                points,
+                due,
+                ..Story::new(name, description)
+            };
+            let story_id = db
+                .create_story(story, epic_id)
+                .with_context(|| anyhow!("failed to create story!"))?;
+            println!("{}", story_id);
+        }
+        StoryCommand::List { epic_id, json } => {
+            let epic = db
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let mut stories = vec![];
+            for story_id in epic.stories {
+                let story = db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story {} in database!", story_id))?;
+                stories.push((story_id, story));
+            }
+            let stories = stories
+                .iter()
+                .map(|(id, story)| Listed {
+                    id: *id,
+                    item: story,
+                })
+                .collect::<Vec<_>>();
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stories)?);
+            } else {
+                for Listed { id, item: story } in stories {
+                    println!("{}\t{}\t{}", id, story.status, story.name);
+                }
+            }
+        }
+        StoryCommand::Status { story_id, status } => {
+            let workflow = db.get_workflow()?;
+            let status = workflow.find_state(&status)?;
+
+            // scripts aren't asked for confirmation, only told
+            if workflow.is_in_progress(&status) {
+                for (blocker_id, blocker) in db.open_blockers(story_id)? {
+                    eprintln!(
+                        "warning: story {} is still blocked by story {} ({}, {})",
+                        story_id, blocker_id, blocker.name, blocker.status
+                    );
+                }
+            }
+            db.update_story_status(story_id, status)
+                .with_context(|| anyhow!("failed to update story!"))?;
+        }
+        StoryCommand::Block {
+            story_id,
+            blocker_id,
+        } => {
+            db.add_blocker(story_id, blocker_id)
+                .with_context(|| anyhow!("failed to add dependency!"))?;
+        }
+        StoryCommand::Unblock {
+            story_id,
+            blocker_id,
+        } => {
+            db.remove_blocker(story_id, blocker_id)
+                .with_context(|| anyhow!("failed to remove dependency!"))?;
+        }
+        StoryCommand::Estimate {
+            story_id,
+            priority,
+            points,
+        } => {
+            db.update_story_estimate(story_id, priority, points)
+                .with_context(|| anyhow!("failed to update story!"))?;
+        }
+        StoryCommand::Assign { story_id, username } => {
+            db.assign_story(story_id, Some(username), None)
+                .with_context(|| anyhow!("failed to assign story!"))?;
+        }
+        StoryCommand::Unassign { story_id } => {
+            db.assign_story(story_id, None, None)
+                .with_context(|| anyhow!("failed to unassign story!"))?;
+        }
+        StoryCommand::Due { story_id, due } => {
+            db.set_story_due(story_id, due)
+                .with_context(|| anyhow!("failed to update story!"))?;
+        }
+        StoryCommand::Label { story_id, label } => {
+            db.add_story_label(story_id, &label)
+                .with_context(|| anyhow!("failed to label story!"))?;
+        }
+        StoryCommand::Unlabel { story_id, label } => {
+            db.remove_story_label(story_id, &label)
+                .with_context(|| anyhow!("failed to unlabel story!"))?;
+        }
+        StoryCommand::Delete { epic_id, story_id } => {
+            db.delete_story(epic_id, story_id)
+                .with_context(|| anyhow!("failed to delete story!"))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run_person_command(db: &JiraDatabase, command: PersonCommand) -> Result<()> {
+    match command {
+        PersonCommand::Add { username, name } => {
+            db.add_person(username.clone(), Person { name })
+                .with_context(|| anyhow!("failed to register {}!", username))?;
+        }
+        PersonCommand::List { json } => {
+            let people = db.get_people()?;
+            let people = people
+                .into_iter()
+                .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                .collect::<Vec<_>>();
+
+            if json {
+                let people = people
+                    .iter()
+                    .map(|(username, person)| {
+                        serde_json::json!({ "username": username, "name": person.name })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&people)?);
+            } else {
+                for (username, person) in people {
+                    println!("{}\t{}", username, person.name);
+                }
+            }
+        }
+        PersonCommand::Remove { username } => {
+            db.remove_person(&username)
+                .with_context(|| anyhow!("failed to remove {}!", username))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run_label_command(db: &JiraDatabase, command: LabelCommand) -> Result<()> {
+    match command {
+        LabelCommand::List { json } => {
+            let db_state = db.read_db()?;
+            let labels = db_state
+                .labels
+                .iter()
+                .sorted_by_key(|(id, _)| **id)
+                .map(|(id, label)| {
+                    let epics = db_state
+                        .epics
+                        .values()
+                        .filter(|epic| epic.labels.contains(id))
+                        .count();
+                    let stories = db_state
+                        .stories
+                        .values()
+                        .filter(|story| story.labels.contains(id))
+                        .count();
+                    (label, epics, stories)
+                })
+                .collect::<Vec<_>>();
+
+            if json {
+                let labels = labels
+                    .iter()
+                    .map(|(label, epics, stories)| {
+                        serde_json::json!({ "name": label.name, "epics": epics, "stories": stories })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&labels)?);
+            } else {
+                for (label, epics, stories) in labels {
+                    println!("{}\t{} epics\t{} stories", label.name, epics, stories);
+                }
+            }
+        }
+        LabelCommand::Rename { label, new_name } => {
+            db.rename_label(&label, &new_name)
+                .with_context(|| anyhow!("failed to rename {}!", label))?;
+        }
+        LabelCommand::Merge { from, into } => {
+            db.merge_labels(&from, &into)
+                .with_context(|| anyhow!("failed to merge {} into {}!", from, into))?;
+        }
+    }
+
+    Ok(())
+}
+
+// How many days a sprint lasts when it is started without an end date.
+const SPRINT_DAYS: u64 = 14;
+
+pub fn run_sprint_command(db: &JiraDatabase, command: SprintCommand) -> Result<()> {
+    let active_sprint = || {
+        db.active_sprint()?.ok_or_else(|| {
+            anyhow!("there is no active sprint, start one with `my-jira sprint start`!")
+        })
+    };
+
+    match command {
+        SprintCommand::Start { name, start, end } => {
+            let start = start.unwrap_or_else(|| Local::now().date_naive());
+            let end = match end {
+                Some(end) => end,
+                None => start
+                    .checked_add_days(Days::new(SPRINT_DAYS - 1))
+                    .ok_or_else(|| anyhow!("invalid sprint start!"))?,
+            };
+            let (sprint_id, carried_over) = db
+                .start_sprint(&name, start, end)
+                .with_context(|| anyhow!("failed to start sprint!"))?;
+            println!("{}", sprint_id);
+            if !carried_over.is_empty() {
+                println!(
+                    "carried over {} unfinished stories: {}",
+                    carried_over.len(),
+                    carried_over.iter().join(", ")
+                );
+            }
+        }
+        SprintCommand::Close => {
+            let (_, unfinished) = db
+                .close_sprint()
+                .with_context(|| anyhow!("failed to close sprint!"))?;
+            if !unfinished.is_empty() {
+                println!(
+                    "{} unfinished stories will carry over to the next sprint: {}",
+                    unfinished.len(),
+                    unfinished.iter().join(", ")
+                );
+            }
+        }
+        SprintCommand::Add { story_id } => {
+            let (sprint_id, _) = active_sprint()?;
+            db.add_sprint_story(sprint_id, story_id)
+                .with_context(|| anyhow!("failed to add story to sprint!"))?;
+        }
+        SprintCommand::Remove { story_id } => {
+            let (sprint_id, _) = active_sprint()?;
+            db.remove_sprint_story(sprint_id, story_id)
+                .with_context(|| anyhow!("failed to remove story from sprint!"))?;
+        }
+        SprintCommand::Show { json } => {
+            let (sprint_id, sprint) = active_sprint()?;
+
+            if json {
+                let sprint = Listed {
+                    id: sprint_id,
+                    item: &sprint,
+                };
+                println!("{}", serde_json::to_string_pretty(&sprint)?);
+            } else {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    sprint_id, sprint.name, sprint.start, sprint.end
+                );
+                let stories = db.read_db()?.stories;
+                for story_id in &sprint.stories {
+                    if let Some(story) = stories.get(story_id) {
+                        println!("{}\t{}\t{}", story_id, story.status, story.name);
+                    }
+                }
+            }
+        }
+        SprintCommand::Burndown => {
+            let (sprint_id, _) = active_sprint()?;
+            print!("{}", db.burndown(sprint_id, Local::now().date_naive())?);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run_export_command(
+    db: &JiraDatabase,
+    format: ExportFormat,
+    epic_ids: Vec<u32>,
+    statuses: Vec<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let workflow = db.get_workflow()?;
+    let filter = ExportFilter {
+        epic_ids,
+        statuses: statuses
+            .iter()
+            .map(|status| workflow.find_state(status))
+            .collect::<Result<_>>()?,
+    };
+    let report = export(&db.read_db()?, format, &filter, Local::now().date_naive())
+        .with_context(|| anyhow!("failed to export the board!"))?;
+
+    match output {
+        Some(path) => fs::write(&path, report)
+            .with_context(|| anyhow!("failed to write {}!", path.display()))?,
+        None => print!("{}", report),
+    }
+
+    Ok(())
+}
+
+pub fn run_log_command(
+    db: &JiraDatabase,
+    epic_id: Option<u32>,
+    story_id: Option<u32>,
+    limit: Option<usize>,
+    json: bool,
+) -> Result<()> {
+    let events = match (epic_id, story_id) {
+        (Some(epic_id), _) => db.get_history(ItemKind::Epic, epic_id)?,
+        (_, Some(story_id)) => db.get_history(ItemKind::Story, story_id)?,
+        (None, None) => db.get_events()?,
+    };
+    let skipped = limit.map_or(0, |limit| events.len().saturating_sub(limit));
+    let events = &events[skipped..];
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(events)?);
+    } else {
+        for event in events {
+            println!("{}", event);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::BTreeMap;
+
+    use crate::{
+        db::test_utils::MockDB,
+        models::{Priority, Status, Workflow},
+    };
+
+    fn mock_db() -> JiraDatabase {
+        JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        }
+    }
+
+    #[test]
+    fn epic_commands_should_update_the_board() {
+        let db = mock_db();
+
+        run_epic_command(
+            &db,
+            EpicCommand::Create {
+                name: "name".to_owned(),
+                description: "description".to_owned(),
+                due: NaiveDate::from_ymd_opt(2026, 11, 1),
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_epic(1).unwrap().unwrap().name, "name");
+
+        run_epic_command(
+            &db,
+            EpicCommand::Due {
+                epic_id: 1,
+                due: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_epic(1).unwrap().unwrap().due, None);
+
+        run_epic_command(
+            &db,
+            EpicCommand::Status {
+                epic_id: 1,
+                status: "closed".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_epic(1).unwrap().unwrap().status, Status::CLOSED);
+
+        assert!(run_epic_command(&db, EpicCommand::List { json: true }).is_ok());
+
+        run_epic_command(&db, EpicCommand::Delete { epic_id: 1 }).unwrap();
+        assert!(db.get_epics().unwrap().is_empty());
+    }
+
+    #[test]
+    fn story_commands_should_update_the_board() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        run_story_command(
+            &db,
+            StoryCommand::Create {
+                epic_id,
+                name: "name".to_owned(),
+                description: "".to_owned(),
+                priority: Priority::High,
+                points: Some(3),
+                due: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().stories, vec![2]);
+        assert_eq!(db.get_story(2).unwrap().unwrap().points, Some(3));
+
+        let due = NaiveDate::from_ymd_opt(2026, 11, 1);
+        run_story_command(&db, StoryCommand::Due { story_id: 2, due }).unwrap();
+        assert_eq!(db.get_story(2).unwrap().unwrap().due, due);
+
+        let blocker_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        run_story_command(
+            &db,
+            StoryCommand::Block {
+                story_id: 2,
+                blocker_id,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_story(2).unwrap().unwrap().blocked_by,
+            vec![blocker_id]
+        );
+        run_story_command(
+            &db,
+            StoryCommand::Unblock {
+                story_id: 2,
+                blocker_id,
+            },
+        )
+        .unwrap();
+        assert!(db.get_story(2).unwrap().unwrap().blocked_by.is_empty());
+
+        run_story_command(
+            &db,
+            StoryCommand::Estimate {
+                story_id: 2,
+                priority: Priority::Low,
+                points: None,
+            },
+        )
+        .unwrap();
+        let story = db.get_story(2).unwrap().unwrap();
+        assert_eq!(story.priority, Priority::Low);
+        assert_eq!(story.points, None);
+
+        run_story_command(
+            &db,
+            StoryCommand::Status {
+                story_id: 2,
+                status: "RESOLVED".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_story(2).unwrap().unwrap().status, Status::RESOLVED);
+
+        assert!(run_story_command(
+            &db,
+            StoryCommand::List {
+                epic_id,
+                json: false
+            }
+        )
+        .is_ok());
+
+        run_story_command(
+            &db,
+            StoryCommand::Delete {
+                epic_id,
+                story_id: 2,
+            },
+        )
+        .unwrap();
+        assert!(db.get_story(2).unwrap().is_none());
+    }
+
+    #[test]
+    fn person_commands_should_register_and_assign_people() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        run_person_command(
+            &db,
+            PersonCommand::Add {
+                username: "alice".to_owned(),
+                name: "Alice".to_owned(),
+            },
+        )
+        .unwrap();
+        run_epic_command(
+            &db,
+            EpicCommand::Assign {
+                epic_id,
+                username: "alice".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_epic(epic_id).unwrap().unwrap().assignee.as_deref(),
+            Some("alice")
+        );
+
+        assert!(run_epic_command(
+            &db,
+            EpicCommand::Assign {
+                epic_id,
+                username: "bob".to_owned(),
+            },
+        )
+        .is_err());
+
+        run_person_command(
+            &db,
+            PersonCommand::Remove {
+                username: "alice".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().assignee, None);
+    }
+
+    #[test]
+    fn sprint_commands_should_work_on_the_active_sprint() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(run_sprint_command(&db, SprintCommand::Add { story_id }).is_err());
+
+        let start = NaiveDate::from_ymd_opt(2026, 11, 2).unwrap();
+        run_sprint_command(
+            &db,
+            SprintCommand::Start {
+                name: "Sprint 1".to_owned(),
+                start: Some(start),
+                end: None,
+            },
+        )
+        .unwrap();
+        run_sprint_command(&db, SprintCommand::Add { story_id }).unwrap();
+
+        let (_, sprint) = db.active_sprint().unwrap().unwrap();
+        // two weeks, the start day included
+        assert_eq!(sprint.end, NaiveDate::from_ymd_opt(2026, 11, 15).unwrap());
+        assert_eq!(sprint.stories, vec![story_id]);
+
+        run_sprint_command(&db, SprintCommand::Close).unwrap();
+        assert!(db.active_sprint().unwrap().is_none());
+        assert!(run_sprint_command(&db, SprintCommand::Burndown).is_err());
+    }
+
+    #[test]
+    fn export_command_should_write_the_report() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("Accounts".to_owned(), "".to_owned()))
+            .unwrap();
+        db.create_story(Story::new("Login".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.html");
+
+        run_export_command(
+            &db,
+            ExportFormat::Html,
+            vec![epic_id],
+            vec!["open".to_owned()],
+            Some(path.clone()),
+        )
+        .unwrap();
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(report.contains("Accounts"));
+        assert!(report.contains("<td>Login</td>"));
+
+        assert!(run_export_command(
+            &db,
+            ExportFormat::Md,
+            vec![],
+            vec!["someday".to_owned()],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn label_commands_should_rename_and_merge_labels() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        run_epic_command(
+            &db,
+            EpicCommand::Label {
+                epic_id,
+                label: "backend".to_owned(),
+            },
+        )
+        .unwrap();
+        run_story_command(
+            &db,
+            StoryCommand::Label {
+                story_id,
+                label: "api".to_owned(),
+            },
+        )
+        .unwrap();
+
+        run_label_command(
+            &db,
+            LabelCommand::Rename {
+                label: "API".to_owned(),
+                new_name: "Backend".to_owned(),
+            },
+        )
+        .unwrap_err();
+
+        run_label_command(
+            &db,
+            LabelCommand::Merge {
+                from: "api".to_owned(),
+                into: "backend".to_owned(),
+            },
+        )
+        .unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().labels, vec![1]);
+        assert_eq!(db.get_labels().unwrap().len(), 1);
+
+        assert!(run_label_command(&db, LabelCommand::List { json: true }).is_ok());
+
+        run_story_command(
+            &db,
+            StoryCommand::Unlabel {
+                story_id,
+                label: "Backend".to_owned(),
+            },
+        )
+        .unwrap();
+        assert!(db.get_story(story_id).unwrap().unwrap().labels.is_empty());
+    }
+
+    #[test]
+    fn log_command_should_show_the_history() {
+        let db = mock_db();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(run_log_command(&db, None, None, Some(1), false).is_ok());
+        assert!(run_log_command(&db, Some(epic_id), None, None, true).is_ok());
+        assert!(run_log_command(&db, None, Some(999), None, false).is_ok());
+    }
+
+    #[test]
+    fn commands_should_fail_for_unknown_ids() {
+        let db = mock_db();
+
+        assert!(run_epic_command(&db, EpicCommand::Delete { epic_id: 1 }).is_err());
+        assert!(run_story_command(
+            &db,
+            StoryCommand::Status {
+                story_id: 7,
+                status: "resolved".to_owned()
+            }
+        )
+        .is_err());
+        assert!(run_story_command(
+            &db,
+            StoryCommand::List {
+                epic_id: 1,
+                json: true
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn status_commands_should_follow_the_workflow() {
+        let db = mock_db();
+        let transitions = BTreeMap::from([("Todo".to_owned(), vec!["Done".to_owned()])]);
+        db.set_workflow(Workflow::new(&["Todo", "Done"], &["Done"], Some(&transitions)).unwrap())
+            .unwrap();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let set_status = |status: &str| {
+            run_story_command(
+                &db,
+                StoryCommand::Status {
+                    story_id,
+                    status: status.to_owned(),
+                },
+            )
+        };
+        assert!(set_status("closed").is_err());
+        set_status("done").unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::new("Done")
+        );
+        assert!(set_status("todo").is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use crate::{db::Backend, models::Workflow};
+
+// Example `$XDG_CONFIG_HOME/my-jira/config.toml`:
+//
+//     user = "alice"
+//     default_board = "work"
+//
+//     [boards.work]
+//     path = "~/boards/work.json"
+//
+//     [boards.side-project]
+//     path = "side-project.sqlite"
+//     backend = "sqlite"
+//
+//     [boards.side-project.workflow]
+//     states = ["Todo", "Doing", "Review", "Done"]
+//     terminal = ["Done"]
+//
+//     [boards.side-project.workflow.transitions]
+//     Todo = ["Doing"]
+//     Doing = ["Todo", "Review"]
+//     Review = ["Doing", "Done"]
+//
+// Relative board paths are resolved against the directory holding the config file. Boards
+// without a workflow keep the one stored with them, which starts as OPEN, IN PROGRESS,
+// RESOLVED and CLOSED with any transition allowed.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+pub struct Config {
+    pub user: Option<String>,
+    pub default_board: Option<String>,
+    #[serde(default)]
+    pub boards: BTreeMap<String, BoardConfig>,
+    #[serde(skip)]
+    base_dir: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BoardConfig {
+    pub path: String,
+    pub backend: Option<Backend>,
+    pub workflow: Option<WorkflowConfig>,
+}
+
+// New items start in the first state. Without transitions any state can move to any other one.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkflowConfig {
+    pub states: Vec<String>,
+    #[serde(default)]
+    pub terminal: Vec<String>,
+    pub transitions: Option<BTreeMap<String, Vec<String>>>,
+}
+
+impl WorkflowConfig {
+    fn to_workflow(&self) -> Result<Workflow> {
+        let states = self.states.iter().map(String::as_str).collect::<Vec<_>>();
+        let terminal = self.terminal.iter().map(String::as_str).collect::<Vec<_>>();
+        Workflow::new(&states, &terminal, self.transitions.as_ref())
+    }
+}
+
+// A board the tool can open, wherever it was configured from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    pub name: Option<String>,
+    pub path: String,
+    pub backend: Backend,
+    // stored with the board when it is opened
+    pub workflow: Option<Workflow>,
+}
+
+impl Board {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.path)
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|config_dir| config_dir.join("my-jira").join("config.toml"))
+}
+
+impl Config {
+    // A missing config file is not an error, the tool works without one.
+    pub fn load() -> Result<Self> {
+        match config_path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("failed to parse config file {}", path.display()))?;
+
+        config.base_dir = path.parent().map(Path::to_path_buf);
+        Ok(config)
+    }
+
+    // The username "My work" shows stories for, falling back to the login name.
+    pub fn current_user(&self) -> Option<String> {
+        self.user
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .filter(|user| !user.is_empty())
+    }
+
+    pub fn board(&self, name: &str) -> Result<Board> {
+        let board_config = self
+            .boards
+            .get(name)
+            .ok_or_else(|| anyhow!("no board named {} in the config file", name))?;
+
+        let path = self.resolve_path(&board_config.path);
+        let backend = board_config
+            .backend
+            .unwrap_or_else(|| Backend::from_path(&path));
+        let workflow = board_config
+            .workflow
+            .as_ref()
+            .map(WorkflowConfig::to_workflow)
+            .transpose()
+            .with_context(|| format!("invalid workflow for board {}", name))?;
+
+        Ok(Board {
+            name: Some(name.to_owned()),
+            path,
+            backend,
+            workflow,
+        })
+    }
+
+    pub fn boards(&self) -> Result<Vec<Board>> {
+        self.boards.keys().map(|name| self.board(name)).collect()
+    }
+
+    fn resolve_path(&self, path: &str) -> String {
+        if let (Some(rest), Some(home_dir)) = (path.strip_prefix("~/"), dirs::home_dir()) {
+            return home_dir.join(rest).to_string_lossy().into_owned();
+        }
+
+        match &self.base_dir {
+            Some(base_dir) if Path::new(path).is_relative() => {
+                base_dir.join(path).to_string_lossy().into_owned()
+            }
+            _ => path.to_owned(),
+        }
+    }
+
+    // Picks the board to open, in order of precedence: an explicit path (`--db` or
+    // `MY_JIRA_DB`), a named board (`--board`), the config's default board and finally the
+    // backend's default file in `./data`.
+    pub fn resolve_board(
+        &self,
+        db_path: Option<String>,
+        board_name: Option<String>,
+        backend: Option<Backend>,
+    ) -> Result<Board> {
+        let mut board = if let Some(path) = db_path {
+            Board {
+                name: None,
+                backend: Backend::from_path(&path),
+                path,
+                workflow: None,
+            }
+        } else if let Some(name) = board_name.or_else(|| self.default_board.clone()) {
+            self.board(&name)?
+        } else {
+            let backend = backend.unwrap_or(Backend::Json);
+            Board {
+                name: None,
+                path: backend.default_file_path().to_owned(),
+                backend,
+                workflow: None,
+            }
+        };
+
+        if let Some(backend) = backend {
+            board.backend = backend;
+        }
+
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Status;
+
+    fn write_config(content: &str) -> (Config, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, content).unwrap();
+
+        (Config::load_from(&path).unwrap(), dir)
+    }
+
+    #[test]
+    fn load_from_should_fail_with_invalid_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "default_board = ").unwrap();
+
+        assert!(Config::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn board_should_resolve_relative_paths_and_backends() {
+        let (config, dir) = write_config(
+            r#"
+            [boards.work]
+            path = "work.json"
+
+            [boards.side]
+            path = "/tmp/side.db"
+            backend = "json"
+
+            [boards.home]
+            path = "home.sqlite"
+            "#,
+        );
+
+        let work = config.board("work").unwrap();
+        assert_eq!(work.name.as_deref(), Some("work"));
+        assert_eq!(
+            work.path,
+            dir.path().join("work.json").to_string_lossy().into_owned()
+        );
+        assert_eq!(work.backend, Backend::Json);
+
+        let side = config.board("side").unwrap();
+        assert_eq!(side.path, "/tmp/side.db");
+        assert_eq!(side.backend, Backend::Json);
+
+        assert_eq!(config.board("home").unwrap().backend, Backend::Sqlite);
+        assert!(config.board("missing").is_err());
+        assert_eq!(config.boards().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn resolve_board_should_follow_precedence() {
+        let (config, _dir) = write_config(
+            r#"
+            default_board = "work"
+
+            [boards.work]
+            path = "/boards/work.json"
+
+            [boards.home]
+            path = "/boards/home.json"
+            "#,
+        );
+
+        let board = config
+            .resolve_board(
+                Some("/elsewhere/db.sqlite".to_owned()),
+                Some("home".to_owned()),
+                None,
+            )
+            .unwrap();
+        assert_eq!(board.path, "/elsewhere/db.sqlite");
+        assert_eq!(board.backend, Backend::Sqlite);
+
+        let board = config
+            .resolve_board(None, Some("home".to_owned()), None)
+            .unwrap();
+        assert_eq!(board.path, "/boards/home.json");
+
+        let board = config.resolve_board(None, None, None).unwrap();
+        assert_eq!(board.path, "/boards/work.json");
+
+        let board = config
+            .resolve_board(None, None, Some(Backend::Sqlite))
+            .unwrap();
+        assert_eq!(board.path, "/boards/work.json");
+        assert_eq!(board.backend, Backend::Sqlite);
+    }
+
+    #[test]
+    fn board_should_load_its_workflow() {
+        let (config, _dir) = write_config(
+            r#"
+            [boards.work]
+            path = "work.json"
+
+            [boards.work.workflow]
+            states = ["Todo", "Doing", "Done"]
+            terminal = ["done"]
+
+            [boards.work.workflow.transitions]
+            Todo = ["Doing"]
+            Doing = ["Done"]
+
+            [boards.broken]
+            path = "broken.json"
+
+            [boards.broken.workflow]
+            states = ["Todo"]
+            terminal = ["Done"]
+            "#,
+        );
+
+        let workflow = config.board("work").unwrap().workflow.unwrap();
+        assert_eq!(workflow.initial_state(), &Status::new("Todo"));
+        assert_eq!(workflow.terminal, vec![Status::new("Done")]);
+        assert_eq!(
+            workflow.next_states(&Status::new("Doing")),
+            [Status::new("Done")]
+        );
+        assert!(config.board("broken").is_err());
+        assert!(config
+            .resolve_board(Some("db.json".to_owned()), None, None)
+            .unwrap()
+            .workflow
+            .is_none());
+    }
+
+    #[test]
+    fn current_user_should_prefer_the_config_file() {
+        let (config, _dir) = write_config(r#"user = "alice""#);
+
+        assert_eq!(config.current_user().as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn resolve_board_should_fall_back_to_default_path() {
+        let config = Config::default();
+
+        let board = config.resolve_board(None, None, None).unwrap();
+        assert_eq!(board.path, Backend::Json.default_file_path());
+
+        let board = config
+            .resolve_board(None, None, Some(Backend::Sqlite))
+            .unwrap();
+        assert_eq!(board.path, Backend::Sqlite.default_file_path());
+    }
+}
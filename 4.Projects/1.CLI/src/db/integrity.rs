@@ -0,0 +1,484 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+
+use crate::models::{DBState, Epic, Person, Status};
+
+pub const RECOVERED_EPIC_NAME: &str = "Recovered stories";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Problem {
+    LastItemIdRegression { last_item_id: u32, max_id: u32 },
+    IdUsedByEpicAndStory { id: u32 },
+    DanglingStoryReference { epic_id: u32, story_id: u32 },
+    DuplicateStoryReference { story_id: u32, epic_ids: Vec<u32> },
+    OrphanedStory { story_id: u32 },
+    UnknownAssignee { item_id: u32, username: String },
+    UnknownLabel { item_id: u32, label_id: u32 },
+    DanglingBlocker { story_id: u32, blocker_id: u32 },
+    UnknownStatus { item_id: u32, status: Status },
+    DanglingSprintStory { sprint_id: u32, story_id: u32 },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::LastItemIdRegression {
+                last_item_id,
+                max_id,
+            } => write!(
+                f,
+                "last_item_id is {} but id {} is already in use",
+                last_item_id, max_id
+            ),
+            Problem::IdUsedByEpicAndStory { id } => {
+                write!(f, "id {} is used by both an epic and a story", id)
+            }
+            Problem::DanglingStoryReference { epic_id, story_id } => write!(
+                f,
+                "epic {} references story {} which does not exist",
+                epic_id, story_id
+            ),
+            Problem::DuplicateStoryReference { story_id, epic_ids } => write!(
+                f,
+                "story {} is referenced more than once (by epics {})",
+                story_id,
+                epic_ids.iter().join(", ")
+            ),
+            Problem::OrphanedStory { story_id } => {
+                write!(f, "story {} does not belong to any epic", story_id)
+            }
+            Problem::UnknownAssignee { item_id, username } => write!(
+                f,
+                "item {} is assigned to {} who is not a registered team member",
+                item_id, username
+            ),
+            Problem::UnknownLabel { item_id, label_id } => write!(
+                f,
+                "item {} carries label {} which does not exist",
+                item_id, label_id
+            ),
+            Problem::DanglingBlocker {
+                story_id,
+                blocker_id,
+            } => write!(
+                f,
+                "story {} is blocked by story {} which does not exist",
+                story_id, blocker_id
+            ),
+            Problem::UnknownStatus { item_id, status } => write!(
+                f,
+                "item {} is in status {} which the board's workflow does not have",
+                item_id, status
+            ),
+            Problem::DanglingSprintStory {
+                sprint_id,
+                story_id,
+            } => write!(
+                f,
+                "sprint {} plans story {} which does not exist",
+                sprint_id, story_id
+            ),
+        }
+    }
+}
+
+fn max_id(db_state: &DBState) -> u32 {
+    db_state
+        .epics
+        .keys()
+        .chain(db_state.stories.keys())
+        .copied()
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn check(db_state: &DBState) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    let max_id = max_id(db_state);
+    if db_state.last_item_id < max_id {
+        problems.push(Problem::LastItemIdRegression {
+            last_item_id: db_state.last_item_id,
+            max_id,
+        });
+    }
+
+    for id in db_state.epics.keys().sorted() {
+        if db_state.stories.contains_key(id) {
+            problems.push(Problem::IdUsedByEpicAndStory { id: *id });
+        }
+    }
+
+    let mut references: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for epic_id in db_state.epics.keys().sorted() {
+        for story_id in &db_state.epics[epic_id].stories {
+            if db_state.stories.contains_key(story_id) {
+                references.entry(*story_id).or_default().push(*epic_id);
+            } else {
+                problems.push(Problem::DanglingStoryReference {
+                    epic_id: *epic_id,
+                    story_id: *story_id,
+                });
+            }
+        }
+    }
+
+    for (story_id, epic_ids) in &references {
+        if epic_ids.len() > 1 {
+            problems.push(Problem::DuplicateStoryReference {
+                story_id: *story_id,
+                epic_ids: epic_ids.clone(),
+            });
+        }
+    }
+
+    for story_id in db_state.stories.keys().sorted() {
+        if !references.contains_key(story_id) {
+            problems.push(Problem::OrphanedStory {
+                story_id: *story_id,
+            });
+        }
+    }
+
+    for (item_id, assignee) in assignees(db_state) {
+        if !db_state.people.contains_key(assignee) {
+            problems.push(Problem::UnknownAssignee {
+                item_id,
+                username: assignee.to_owned(),
+            });
+        }
+    }
+
+    for (item_id, label_id) in label_references(db_state) {
+        if !db_state.labels.contains_key(&label_id) {
+            problems.push(Problem::UnknownLabel { item_id, label_id });
+        }
+    }
+
+    for story_id in db_state.stories.keys().sorted() {
+        for blocker_id in &db_state.stories[story_id].blocked_by {
+            if !db_state.stories.contains_key(blocker_id) {
+                problems.push(Problem::DanglingBlocker {
+                    story_id: *story_id,
+                    blocker_id: *blocker_id,
+                });
+            }
+        }
+    }
+
+    for (item_id, status) in statuses(db_state) {
+        if !db_state.workflow.contains(status) {
+            problems.push(Problem::UnknownStatus {
+                item_id,
+                status: status.clone(),
+            });
+        }
+    }
+
+    for sprint_id in db_state.sprints.keys().sorted() {
+        for story_id in &db_state.sprints[sprint_id].stories {
+            if !db_state.stories.contains_key(story_id) {
+                problems.push(Problem::DanglingSprintStory {
+                    sprint_id: *sprint_id,
+                    story_id: *story_id,
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+fn statuses(db_state: &DBState) -> Vec<(u32, &Status)> {
+    let epic_statuses = db_state.epics.iter().map(|(id, epic)| (*id, &epic.status));
+    let story_statuses = db_state
+        .stories
+        .iter()
+        .map(|(id, story)| (*id, &story.status));
+
+    epic_statuses.chain(story_statuses).sorted().collect()
+}
+
+fn label_references(db_state: &DBState) -> Vec<(u32, u32)> {
+    let epic_labels = db_state
+        .epics
+        .iter()
+        .flat_map(|(id, epic)| epic.labels.iter().map(move |label_id| (*id, *label_id)));
+    let story_labels = db_state
+        .stories
+        .iter()
+        .flat_map(|(id, story)| story.labels.iter().map(move |label_id| (*id, *label_id)));
+
+    epic_labels.chain(story_labels).sorted().collect()
+}
+
+fn assignees(db_state: &DBState) -> Vec<(u32, &str)> {
+    let epic_assignees = db_state
+        .epics
+        .iter()
+        .filter_map(|(id, epic)| Some((*id, epic.assignee.as_deref()?)));
+    let story_assignees = db_state
+        .stories
+        .iter()
+        .filter_map(|(id, story)| Some((*id, story.assignee.as_deref()?)));
+
+    epic_assignees.chain(story_assignees).sorted().collect()
+}
+
+// Fixes every problem `check` reports without dropping any story: stories that share their
+// id with an epic get a fresh id, orphaned stories are collected in a new epic, unknown
+// assignees are added to the people registry, references to missing labels, blockers or sprint
+// stories are dropped and items in a status the workflow doesn't have go back to its initial state.
+pub fn repair(db_state: &mut DBState) -> Vec<Problem> {
+    let problems = check(db_state);
+
+    db_state.last_item_id = db_state.last_item_id.max(max_id(db_state));
+
+    let colliding_ids = db_state
+        .epics
+        .keys()
+        .filter(|id| db_state.stories.contains_key(id))
+        .copied()
+        .sorted()
+        .collect::<Vec<_>>();
+    for old_id in colliding_ids {
+        db_state.last_item_id += 1;
+        let new_id = db_state.last_item_id;
+
+        if let Some(story) = db_state.stories.remove(&old_id) {
+            db_state.stories.insert(new_id, story);
+        }
+        for epic in db_state.epics.values_mut() {
+            for story_id in epic.stories.iter_mut().filter(|id| **id == old_id) {
+                *story_id = new_id;
+            }
+        }
+        for story in db_state.stories.values_mut() {
+            for blocker_id in story.blocked_by.iter_mut().filter(|id| **id == old_id) {
+                *blocker_id = new_id;
+            }
+        }
+        for sprint in db_state.sprints.values_mut() {
+            for story_id in sprint.stories.iter_mut().filter(|id| **id == old_id) {
+                *story_id = new_id;
+            }
+        }
+    }
+
+    let mut referenced = HashSet::new();
+    for epic_id in db_state.epics.keys().copied().sorted().collect::<Vec<_>>() {
+        let stories = &db_state.stories;
+        db_state
+            .epics
+            .get_mut(&epic_id)
+            .expect("epic ids were just collected")
+            .stories
+            .retain(|story_id| stories.contains_key(story_id) && referenced.insert(*story_id));
+    }
+
+    let orphaned_story_ids = db_state
+        .stories
+        .keys()
+        .filter(|id| !referenced.contains(id))
+        .copied()
+        .sorted()
+        .collect::<Vec<_>>();
+    let unknown_assignees = assignees(db_state)
+        .into_iter()
+        .map(|(_, assignee)| assignee.to_owned())
+        .filter(|assignee| !db_state.people.contains_key(assignee))
+        .collect::<Vec<_>>();
+    for username in unknown_assignees {
+        db_state.people.insert(
+            username.clone(),
+            Person {
+                name: username.clone(),
+            },
+        );
+    }
+
+    let labels = &db_state.labels;
+    for epic in db_state.epics.values_mut() {
+        epic.labels.retain(|label_id| labels.contains_key(label_id));
+    }
+    for story in db_state.stories.values_mut() {
+        story
+            .labels
+            .retain(|label_id| labels.contains_key(label_id));
+    }
+
+    let story_ids = db_state.stories.keys().copied().collect::<HashSet<_>>();
+    for story in db_state.stories.values_mut() {
+        story
+            .blocked_by
+            .retain(|blocker_id| story_ids.contains(blocker_id));
+    }
+    for sprint in db_state.sprints.values_mut() {
+        sprint
+            .stories
+            .retain(|story_id| story_ids.contains(story_id));
+    }
+
+    let workflow = &db_state.workflow;
+    let statuses = db_state
+        .epics
+        .values_mut()
+        .map(|epic| &mut epic.status)
+        .chain(db_state.stories.values_mut().map(|story| &mut story.status));
+    for status in statuses.filter(|status| !workflow.contains(status)) {
+        *status = workflow.initial_state().clone();
+    }
+
+    if !orphaned_story_ids.is_empty() {
+        db_state.last_item_id += 1;
+
+        let mut epic = Epic::new(
+            RECOVERED_EPIC_NAME.to_owned(),
+            "Stories found by `my-jira fsck --repair` that did not belong to any epic".to_owned(),
+        );
+        epic.status = db_state.workflow.initial_state().clone();
+        epic.stories = orphaned_story_ids;
+        db_state.epics.insert(db_state.last_item_id, epic);
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use chrono::NaiveDate;
+
+    use crate::models::{Sprint, Story, Workflow};
+
+    fn broken_state() -> DBState {
+        let mut epic_1 = Epic::new("".to_owned(), "".to_owned());
+        epic_1.stories = vec![2, 3, 99];
+        let mut epic_4 = Epic::new("".to_owned(), "".to_owned());
+        epic_4.stories = vec![3, 4];
+
+        let mut state = DBState {
+            last_item_id: 3,
+            epics: HashMap::new(),
+            stories: HashMap::new(),
+            people: HashMap::new(),
+            labels: HashMap::new(),
+            workflow: Workflow::default(),
+            sprints: HashMap::new(),
+        };
+        state.epics.insert(1, epic_1);
+        state.epics.insert(4, epic_4);
+        for id in [2, 3, 4, 5] {
+            state
+                .stories
+                .insert(id, Story::new(format!("story {}", id), "".to_owned()));
+        }
+        state.stories.get_mut(&2).unwrap().assignee = Some("ghost".to_owned());
+        state.stories.get_mut(&3).unwrap().labels = vec![7];
+        state.stories.get_mut(&2).unwrap().blocked_by = vec![4];
+        state.stories.get_mut(&5).unwrap().blocked_by = vec![98];
+        state.stories.get_mut(&3).unwrap().status = Status::new("Limbo");
+        let mut sprint = Sprint::new(
+            "".to_owned(),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+        );
+        sprint.stories = vec![4, 97];
+        state.sprints.insert(1, sprint);
+        state
+    }
+
+    #[test]
+    fn check_should_find_nothing_on_empty_board() {
+        let state = DBState {
+            last_item_id: 0,
+            epics: HashMap::new(),
+            stories: HashMap::new(),
+            people: HashMap::new(),
+            labels: HashMap::new(),
+            workflow: Workflow::default(),
+            sprints: HashMap::new(),
+        };
+
+        assert!(check(&state).is_empty());
+    }
+
+    #[test]
+    fn check_should_report_every_problem() {
+        let problems = check(&broken_state());
+
+        assert_eq!(
+            problems,
+            vec![
+                Problem::LastItemIdRegression {
+                    last_item_id: 3,
+                    max_id: 5
+                },
+                Problem::IdUsedByEpicAndStory { id: 4 },
+                Problem::DanglingStoryReference {
+                    epic_id: 1,
+                    story_id: 99
+                },
+                Problem::DuplicateStoryReference {
+                    story_id: 3,
+                    epic_ids: vec![1, 4]
+                },
+                Problem::OrphanedStory { story_id: 5 },
+                Problem::UnknownAssignee {
+                    item_id: 2,
+                    username: "ghost".to_owned()
+                },
+                Problem::UnknownLabel {
+                    item_id: 3,
+                    label_id: 7
+                },
+                Problem::DanglingBlocker {
+                    story_id: 5,
+                    blocker_id: 98
+                },
+                Problem::UnknownStatus {
+                    item_id: 3,
+                    status: Status::new("Limbo")
+                },
+                Problem::DanglingSprintStory {
+                    sprint_id: 1,
+                    story_id: 97
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn repair_should_leave_a_consistent_board() {
+        let mut state = broken_state();
+
+        let problems = repair(&mut state);
+
+        assert_eq!(problems.len(), 10);
+        assert!(check(&state).is_empty());
+
+        // story 4 collided with epic 4 and got the first fresh id
+        assert_eq!(state.stories[&6].name, "story 4");
+        assert_eq!(state.epics[&1].stories, vec![2, 3]);
+        assert_eq!(state.epics[&4].stories, vec![6]);
+
+        let recovered = &state.epics[&7];
+        assert_eq!(recovered.name, RECOVERED_EPIC_NAME);
+        assert_eq!(recovered.stories, vec![5]);
+        assert_eq!(state.last_item_id, 7);
+        assert_eq!(state.stories.len(), 4);
+        assert_eq!(state.people["ghost"].name, "ghost");
+        assert!(state.stories[&3].labels.is_empty());
+        // links follow the story to its new id
+        assert_eq!(state.stories[&2].blocked_by, vec![6]);
+        assert!(state.stories[&5].blocked_by.is_empty());
+        assert_eq!(state.sprints[&1].stories, vec![6]);
+        assert_eq!(state.stories[&3].status, Status::OPEN);
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+pub const CURRENT_SCHEMA_VERSION: u32 = 10;
+
+type Migration = fn(&mut Value) -> Result<()>;
+
+// MIGRATIONS[n] upgrades a board from schema version n to version n + 1.
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+    migrate_v8_to_v9,
+    migrate_v9_to_v10,
+];
+
+// Boards written before versioning was introduced have no `schema_version` at all.
+pub fn schema_version(board: &Value) -> Result<u32> {
+    match board.get(SCHEMA_VERSION_KEY) {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| anyhow!("invalid schema version {}", version)),
+    }
+}
+
+// Runs every migration between the board's version and the current one.
+// Returns the version the board was at before upgrading.
+pub fn upgrade(board: &mut Value) -> Result<u32> {
+    if !board.is_object() {
+        return Err(anyhow!("board should be a JSON object"));
+    }
+
+    let from_version = schema_version(board)?;
+
+    if from_version > CURRENT_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "board uses schema version {} but this version of my-jira only supports up to {}",
+            from_version,
+            CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    for migration in &MIGRATIONS[from_version as usize..] {
+        migration(board)?;
+    }
+
+    board[SCHEMA_VERSION_KEY] = CURRENT_SCHEMA_VERSION.into();
+    Ok(from_version)
+}
+
+fn migrate_v0_to_v1(_board: &mut Value) -> Result<()> {
+    // version 1 only introduced the `schema_version` field itself
+    Ok(())
+}
+
+// Sets `field` to `value` on every epic or story of the board that doesn't have it yet.
+fn add_item_field(board: &mut Value, collection: &str, field: &str, value: Value) -> Result<()> {
+    let Some(items) = board.get_mut(collection) else {
+        return Ok(());
+    };
+    let items = items
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("board {} should be a JSON object", collection))?;
+
+    for item in items.values_mut() {
+        item.as_object_mut()
+            .ok_or_else(|| anyhow!("board items should be JSON objects"))?
+            .entry(field)
+            .or_insert(value.clone());
+    }
+    Ok(())
+}
+
+fn migrate_v1_to_v2(board: &mut Value) -> Result<()> {
+    // version 2 added the people registry and assignees on epics and stories
+    board
+        .as_object_mut()
+        .expect("board was checked to be an object")
+        .entry("people")
+        .or_insert_with(|| serde_json::json!({}));
+
+    add_item_field(board, "epics", "assignee", Value::Null)?;
+    add_item_field(board, "stories", "assignee", Value::Null)
+}
+
+fn migrate_v2_to_v3(board: &mut Value) -> Result<()> {
+    // version 3 added priorities and story point estimates to stories
+    add_item_field(board, "stories", "priority", "Medium".into())?;
+    add_item_field(board, "stories", "points", Value::Null)
+}
+
+fn migrate_v3_to_v4(board: &mut Value) -> Result<()> {
+    // version 4 added the label registry and labels on epics and stories
+    board
+        .as_object_mut()
+        .expect("board was checked to be an object")
+        .entry("labels")
+        .or_insert_with(|| serde_json::json!({}));
+
+    add_item_field(board, "epics", "labels", serde_json::json!([]))?;
+    add_item_field(board, "stories", "labels", serde_json::json!([]))
+}
+
+fn migrate_v4_to_v5(board: &mut Value) -> Result<()> {
+    // version 5 added comment threads to stories
+    add_item_field(board, "stories", "comments", serde_json::json!([]))
+}
+
+fn migrate_v5_to_v6(board: &mut Value) -> Result<()> {
+    // version 6 added due dates to epics and stories
+    add_item_field(board, "epics", "due", Value::Null)?;
+    add_item_field(board, "stories", "due", Value::Null)
+}
+
+fn migrate_v6_to_v7(board: &mut Value) -> Result<()> {
+    // version 7 added checklists to stories
+    add_item_field(board, "stories", "checklist", serde_json::json!([]))
+}
+
+fn migrate_v7_to_v8(board: &mut Value) -> Result<()> {
+    // version 8 added dependencies between stories
+    add_item_field(board, "stories", "blocked_by", serde_json::json!([]))
+}
+
+fn migrate_v8_to_v9(board: &mut Value) -> Result<()> {
+    // version 9 stored the board's workflow, which used to be the same four states for every
+    // board with any transition allowed
+    board
+        .as_object_mut()
+        .expect("board was checked to be an object")
+        .entry("workflow")
+        .or_insert_with(|| {
+            serde_json::json!({
+                "states": ["Open", "InProgress", "Resolved", "Closed"],
+                "terminal": ["Resolved", "Closed"],
+                "transitions": {
+                    "Open": ["InProgress", "Resolved", "Closed"],
+                    "InProgress": ["Open", "Resolved", "Closed"],
+                    "Resolved": ["Open", "InProgress", "Closed"],
+                    "Closed": ["Open", "InProgress", "Resolved"]
+                }
+            })
+        });
+    Ok(())
+}
+
+fn migrate_v9_to_v10(board: &mut Value) -> Result<()> {
+    // version 10 added the sprint registry
+    board
+        .as_object_mut()
+        .expect("board was checked to be an object")
+        .entry("sprints")
+        .or_insert_with(|| serde_json::json!({}));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Workflow;
+
+    #[test]
+    fn schema_version_should_default_to_zero() {
+        let board = serde_json::json!({ "last_item_id": 0, "epics": {}, "stories": {} });
+
+        assert_eq!(schema_version(&board).unwrap(), 0);
+    }
+
+    #[test]
+    fn upgrade_should_bring_board_to_current_version() {
+        let mut board = serde_json::json!({ "last_item_id": 0, "epics": {}, "stories": {} });
+
+        let from_version = upgrade(&mut board).unwrap();
+
+        assert_eq!(from_version, 0);
+        assert_eq!(schema_version(&board).unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn migrate_v1_to_v2_should_add_people_and_assignees() {
+        let mut board = serde_json::json!({
+            "schema_version": 1,
+            "last_item_id": 2,
+            "epics": { "1": { "name": "", "description": "", "status": "Open", "stories": [2] } },
+            "stories": { "2": { "name": "", "description": "", "status": "Open" } }
+        });
+
+        assert_eq!(upgrade(&mut board).unwrap(), 1);
+
+        assert_eq!(board["people"], serde_json::json!({}));
+        assert_eq!(board["epics"]["1"]["assignee"], Value::Null);
+        assert_eq!(board["stories"]["2"]["assignee"], Value::Null);
+    }
+
+    #[test]
+    fn migrate_v2_to_v3_should_add_priorities_and_points() {
+        let mut board = serde_json::json!({
+            "schema_version": 2,
+            "stories": { "2": { "name": "", "status": "Open", "points": 3 }, "3": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["stories"]["2"]["priority"], "Medium");
+        assert_eq!(board["stories"]["2"]["points"], 3);
+        assert_eq!(board["stories"]["3"]["points"], Value::Null);
+    }
+
+    #[test]
+    fn migrate_v3_to_v4_should_add_labels() {
+        let mut board = serde_json::json!({
+            "schema_version": 3,
+            "epics": { "1": {} },
+            "stories": { "2": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["labels"], serde_json::json!({}));
+        assert_eq!(board["epics"]["1"]["labels"], serde_json::json!([]));
+        assert_eq!(board["stories"]["2"]["labels"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn migrate_v4_to_v5_should_add_comments() {
+        let mut board = serde_json::json!({
+            "schema_version": 4,
+            "stories": { "2": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["stories"]["2"]["comments"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn migrate_v5_to_v6_should_add_due_dates() {
+        let mut board = serde_json::json!({
+            "schema_version": 5,
+            "epics": { "1": {} },
+            "stories": { "2": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["epics"]["1"]["due"], Value::Null);
+        assert_eq!(board["stories"]["2"]["due"], Value::Null);
+    }
+
+    #[test]
+    fn migrate_v6_to_v7_should_add_checklists() {
+        let mut board = serde_json::json!({
+            "schema_version": 6,
+            "stories": { "2": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["stories"]["2"]["checklist"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn migrate_v7_to_v8_should_add_dependencies() {
+        let mut board = serde_json::json!({
+            "schema_version": 7,
+            "stories": { "2": {} }
+        });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["stories"]["2"]["blocked_by"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn migrate_v8_to_v9_should_add_the_default_workflow() {
+        let mut board = serde_json::json!({ "schema_version": 8 });
+
+        upgrade(&mut board).unwrap();
+
+        let workflow: Workflow = serde_json::from_value(board["workflow"].clone()).unwrap();
+        assert_eq!(workflow, Workflow::default());
+    }
+
+    #[test]
+    fn migrate_v9_to_v10_should_add_sprints() {
+        let mut board = serde_json::json!({ "schema_version": 9 });
+
+        upgrade(&mut board).unwrap();
+
+        assert_eq!(board["sprints"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn upgrade_should_reject_newer_boards() {
+        let mut board = serde_json::json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+
+        assert!(upgrade(&mut board).is_err());
+    }
+
+    #[test]
+    fn upgrade_should_reject_invalid_boards() {
+        let mut not_an_object = serde_json::json!([1, 2, 3]);
+        let mut invalid_version = serde_json::json!({ "schema_version": "one" });
+
+        assert!(upgrade(&mut not_an_object).is_err());
+        assert!(upgrade(&mut invalid_version).is_err());
+    }
+}
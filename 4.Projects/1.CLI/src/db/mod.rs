@@ -0,0 +1,3640 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::{self, File, OpenOptions, TryLockError},
+    io::{Read, Seek, SeekFrom, Write},
+    path::Path,
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use chrono::{NaiveDate, Utc};
+use itertools::Itertools;
+
+use crate::models::{
+    Burndown, Change, ChecklistItem, Comment, DBState, Dependency, Epic, Event, ItemKind, Label,
+    Person, Priority, Sprint, Status, Story, Workflow,
+};
+
+pub mod integrity;
+use integrity::Problem;
+
+pub mod revision;
+use revision::{Items, Revision};
+
+mod migrations;
+use migrations::{CURRENT_SCHEMA_VERSION, SCHEMA_VERSION_KEY};
+
+mod sqlite;
+use sqlite::SqliteDatabase;
+
+#[derive(clap::ValueEnum, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    Json,
+    Sqlite,
+}
+
+impl Backend {
+    pub fn from_path(file_path: &str) -> Self {
+        match Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+        {
+            Some("sqlite" | "sqlite3" | "db") => Backend::Sqlite,
+            _ => Backend::Json,
+        }
+    }
+
+    pub fn default_file_path(&self) -> &'static str {
+        match self {
+            Backend::Json => "./data/db.json",
+            Backend::Sqlite => "./data/db.sqlite",
+        }
+    }
+}
+
+pub struct JiraDatabase {
+    pub database: Box<dyn Database>,
+    // who changes are recorded as in the board's history
+    pub actor: Option<String>,
+    // revisions of the changes made while `record_revision` runs
+    pub recorded: RefCell<Option<Vec<Revision>>>,
+}
+
+impl JiraDatabase {
+    // Boards are only created by `init`, so a mistyped path is reported instead of silently
+    // starting an empty board there.
+    pub fn new(backend: Backend, file_path: String, restore_backup: bool) -> Result<Self> {
+        if !restore_backup && !Path::new(&file_path).exists() {
+            return Err(anyhow!(
+                "{} does not exist, run `my-jira init` to start a board there!",
+                file_path
+            ));
+        }
+
+        let database: Box<dyn Database> = match backend {
+            Backend::Json => {
+                let database = JSONFileDatabase::new(file_path);
+
+                if restore_backup && database.read_db().is_err() {
+                    let _lock = database.lock()?;
+                    database.restore_backup()?;
+                }
+
+                Box::new(database)
+            }
+            Backend::Sqlite => {
+                if restore_backup {
+                    return Err(anyhow!(
+                        "--restore-backup is only supported by the json backend"
+                    ));
+                }
+
+                Box::new(SqliteDatabase::open(file_path)?)
+            }
+        };
+
+        Ok(Self {
+            database,
+            actor: None,
+            recorded: Default::default(),
+        })
+    }
+
+    // Starts an empty board at `file_path`, creating the directories leading to it.
+    pub fn init(backend: Backend, file_path: &str) -> Result<()> {
+        if Path::new(file_path).exists() {
+            return Err(anyhow!("{} already exists!", file_path));
+        }
+        if let Some(dir) = Path::new(file_path).parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        match backend {
+            Backend::Json => {
+                JSONFileDatabase::new(file_path.to_owned()).write_db(&DBState::default())
+            }
+            Backend::Sqlite => SqliteDatabase::open(file_path.to_owned()).map(|_| ()),
+        }
+    }
+
+    pub fn with_actor(mut self, actor: Option<String>) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    pub fn read_db(&self) -> Result<DBState> {
+        self.database.snapshot()
+    }
+
+    pub fn get_epics(&self) -> Result<HashMap<u32, Epic>> {
+        self.database.get_epics()
+    }
+
+    pub fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>> {
+        self.database.get_epic(epic_id)
+    }
+
+    pub fn get_story(&self, story_id: u32) -> Result<Option<Story>> {
+        self.database.get_story(story_id)
+    }
+
+    pub fn get_people(&self) -> Result<HashMap<String, Person>> {
+        self.database.get_people()
+    }
+
+    pub fn add_person(&self, username: String, person: Person) -> Result<()> {
+        self.transaction(|transaction| register_person(transaction, &username, person))
+    }
+
+    // Unassigns the person from everything before removing them from the registry.
+    pub fn remove_person(&self, username: &str) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let db_state = transaction.snapshot()?;
+            if !db_state.people.contains_key(username) {
+                return Err(anyhow!("could not find {} in database!", username));
+            }
+
+            for (epic_id, epic) in db_state.epics {
+                if epic.assignee.as_deref() == Some(username) {
+                    transaction.update_epic(
+                        epic_id,
+                        Epic {
+                            assignee: None,
+                            ..epic
+                        },
+                    )?;
+                    changes.edited(ItemKind::Epic, epic_id, &["assignee"]);
+                }
+            }
+            for (story_id, story) in db_state.stories {
+                if story.assignee.as_deref() == Some(username) {
+                    transaction.update_story(
+                        story_id,
+                        Story {
+                            assignee: None,
+                            ..story
+                        },
+                    )?;
+                    changes.edited(ItemKind::Story, story_id, &["assignee"]);
+                }
+            }
+
+            transaction.delete_person(username)
+        })
+    }
+
+    pub fn get_labels(&self) -> Result<HashMap<u32, Label>> {
+        self.database.get_labels()
+    }
+
+    pub fn get_workflow(&self) -> Result<Workflow> {
+        self.database.get_workflow()
+    }
+
+    // Stores the workflow defined in the config with the board, so it is enforced however the
+    // board is opened. Every epic and story has to be in a state the new workflow knows.
+    pub fn set_workflow(&self, workflow: Workflow) -> Result<()> {
+        if self.get_workflow()? == workflow {
+            return Ok(());
+        }
+
+        self.transaction(|transaction| {
+            let db_state = transaction.snapshot()?;
+
+            let items = db_state
+                .epics
+                .iter()
+                .map(|(id, epic)| (id, &epic.status))
+                .chain(
+                    db_state
+                        .stories
+                        .iter()
+                        .map(|(id, story)| (id, &story.status)),
+                );
+            for (item_id, status) in items.sorted() {
+                if !workflow.contains(status) {
+                    return Err(anyhow!(
+                        "item {} is in status {} which the new workflow does not have!",
+                        item_id,
+                        status
+                    ));
+                }
+            }
+
+            transaction.set_workflow(workflow)
+        })
+    }
+
+    pub fn add_epic_label(&self, epic_id: u32, name: &str) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let label_id = find_or_create_label(transaction, name)?;
+            if !epic.labels.contains(&label_id) {
+                epic.labels.push(label_id);
+                transaction.update_epic(epic_id, epic)?;
+                changes.edited(ItemKind::Epic, epic_id, &["labels"]);
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove_epic_label(&self, epic_id: u32, name: &str) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let label_id = find_label(transaction, name)?;
+            remove_label_id(&mut epic.labels, label_id, name)?;
+            transaction.update_epic(epic_id, epic)?;
+            changes.edited(ItemKind::Epic, epic_id, &["labels"]);
+            Ok(())
+        })
+    }
+
+    pub fn add_story_label(&self, story_id: u32, name: &str) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let label_id = find_or_create_label(transaction, name)?;
+            if !story.labels.contains(&label_id) {
+                story.labels.push(label_id);
+                transaction.update_story(story_id, story)?;
+                changes.edited(ItemKind::Story, story_id, &["labels"]);
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove_story_label(&self, story_id: u32, name: &str) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let label_id = find_label(transaction, name)?;
+            remove_label_id(&mut story.labels, label_id, name)?;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &["labels"]);
+            Ok(())
+        })
+    }
+
+    pub fn rename_label(&self, name: &str, new_name: &str) -> Result<()> {
+        self.transaction(|transaction| {
+            let label_id = find_label(transaction, name)?;
+            let new_name = label_name(new_name)?;
+
+            let labels = transaction.get_labels()?;
+            if labels
+                .iter()
+                .any(|(id, label)| *id != label_id && label.matches(&new_name))
+            {
+                return Err(anyhow!(
+                    "label {} already exists, merge the labels instead!",
+                    new_name
+                ));
+            }
+
+            transaction.update_label(label_id, Label { name: new_name })
+        })
+    }
+
+    // Moves every epic and story labelled `from` over to `into`, then deletes `from`.
+    pub fn merge_labels(&self, from: &str, into: &str) -> Result<()> {
+        self.transaction(|transaction| {
+            let from_id = find_label(transaction, from)?;
+            let into_id = find_label(transaction, into)?;
+            if from_id == into_id {
+                return Err(anyhow!("cannot merge label {} into itself!", from));
+            }
+
+            let db_state = transaction.snapshot()?;
+            for (epic_id, mut epic) in db_state.epics {
+                if replace_label_id(&mut epic.labels, from_id, into_id) {
+                    transaction.update_epic(epic_id, epic)?;
+                }
+            }
+            for (story_id, mut story) in db_state.stories {
+                if replace_label_id(&mut story.labels, from_id, into_id) {
+                    transaction.update_story(story_id, story)?;
+                }
+            }
+
+            transaction.delete_label(from_id)
+        })
+    }
+
+    // New epics and stories start in the initial state of the board's workflow.
+    pub fn create_epic(&self, mut epic: Epic) -> Result<u32> {
+        self.logged_transaction(|transaction, changes| {
+            epic.status = transaction.get_workflow()?.initial_state().clone();
+
+            let new_id = transaction.next_item_id()?;
+            let name = epic.name.clone();
+            transaction.insert_epic(new_id, epic)?;
+            changes.push(ItemKind::Epic, new_id, Change::Created { name });
+            Ok(new_id)
+        })
+    }
+
+    pub fn create_story(&self, mut story: Story, epic_id: u32) -> Result<u32> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+            story.status = transaction.get_workflow()?.initial_state().clone();
+
+            let new_id = transaction.next_item_id()?;
+            let name = story.name.clone();
+            transaction.insert_story(new_id, story)?;
+            changes.push(ItemKind::Story, new_id, Change::Created { name });
+
+            epic.stories.push(new_id);
+            transaction.update_epic(epic_id, epic)?;
+            Ok(new_id)
+        })
+    }
+
+    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            remove_blockers(transaction, changes, &epic.stories)?;
+            remove_sprint_stories(transaction, &epic.stories)?;
+            transaction.delete_epic(epic_id)?;
+            for story_id in epic.stories {
+                let story = transaction.get_story(story_id)?;
+                transaction.delete_story(story_id)?;
+                if let Some(story) = story {
+                    changes.push(
+                        ItemKind::Story,
+                        story_id,
+                        Change::Deleted { name: story.name },
+                    );
+                }
+            }
+            changes.push(ItemKind::Epic, epic_id, Change::Deleted { name: epic.name });
+            Ok(())
+        })
+    }
+
+    pub fn delete_story(&self, epic_id: u32, story_id: u32) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let story_index = epic
+                .stories
+                .iter()
+                .position(|id| id == &story_id)
+                .ok_or_else(|| anyhow!("story id not found in epic stories vector"))?;
+            epic.stories.remove(story_index);
+
+            let story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            remove_blockers(transaction, changes, &[story_id])?;
+            remove_sprint_stories(transaction, &[story_id])?;
+            transaction.update_epic(epic_id, epic)?;
+            transaction.delete_story(story_id)?;
+            changes.push(
+                ItemKind::Story,
+                story_id,
+                Change::Deleted { name: story.name },
+            );
+            Ok(())
+        })
+    }
+
+    pub fn move_story(&self, story_id: u32, from_epic_id: u32, to_epic_id: u32) -> Result<()> {
+        if from_epic_id == to_epic_id {
+            return Err(anyhow!("story is already in epic {}!", to_epic_id));
+        }
+
+        self.logged_transaction(|transaction, changes| {
+            let mut from_epic = transaction
+                .get_epic(from_epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+            let mut to_epic = transaction
+                .get_epic(to_epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let story_index = from_epic
+                .stories
+                .iter()
+                .position(|id| id == &story_id)
+                .ok_or_else(|| anyhow!("story id not found in epic stories vector"))?;
+            from_epic.stories.remove(story_index);
+            to_epic.stories.push(story_id);
+
+            transaction.update_epic(from_epic_id, from_epic)?;
+            transaction.update_epic(to_epic_id, to_epic)?;
+            changes.push(
+                ItemKind::Story,
+                story_id,
+                Change::Moved {
+                    from_epic_id,
+                    to_epic_id,
+                },
+            );
+            Ok(())
+        })
+    }
+
+    pub fn update_epic(&self, epic_id: u32, name: String, description: String) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let fields = changed_fields(&[
+                ("name", epic.name != name),
+                ("description", epic.description != description),
+            ]);
+            epic.name = name;
+            epic.description = description;
+            transaction.update_epic(epic_id, epic)?;
+            changes.edited(ItemKind::Epic, epic_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn update_story(&self, story_id: u32, name: String, description: String) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let fields = changed_fields(&[
+                ("name", story.name != name),
+                ("description", story.description != description),
+            ]);
+            story.name = name;
+            story.description = description;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &fields);
+            Ok(())
+        })
+    }
+
+    // A `new_person` is registered under the assignee's username along with the assignment, so
+    // they are only added if the assignment goes through.
+    pub fn assign_epic(
+        &self,
+        epic_id: u32,
+        assignee: Option<String>,
+        new_person: Option<Person>,
+    ) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            if let (Some(username), Some(person)) = (&assignee, new_person) {
+                register_person(transaction, username, person)?;
+            }
+            check_assignee(transaction, assignee.as_deref())?;
+            let fields = changed_fields(&[("assignee", epic.assignee != assignee)]);
+            epic.assignee = assignee;
+            transaction.update_epic(epic_id, epic)?;
+            changes.edited(ItemKind::Epic, epic_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn assign_story(
+        &self,
+        story_id: u32,
+        assignee: Option<String>,
+        new_person: Option<Person>,
+    ) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            if let (Some(username), Some(person)) = (&assignee, new_person) {
+                register_person(transaction, username, person)?;
+            }
+            check_assignee(transaction, assignee.as_deref())?;
+            let fields = changed_fields(&[("assignee", story.assignee != assignee)]);
+            story.assignee = assignee;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn update_story_estimate(
+        &self,
+        story_id: u32,
+        priority: Priority,
+        points: Option<u32>,
+    ) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let fields = changed_fields(&[
+                ("priority", story.priority != priority),
+                ("points", story.points != points),
+            ]);
+            story.priority = priority;
+            story.points = points;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn set_epic_due(&self, epic_id: u32, due: Option<NaiveDate>) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            let fields = changed_fields(&[("due", epic.due != due)]);
+            epic.due = due;
+            transaction.update_epic(epic_id, epic)?;
+            changes.edited(ItemKind::Epic, epic_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn set_story_due(&self, story_id: u32, due: Option<NaiveDate>) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let fields = changed_fields(&[("due", story.due != due)]);
+            story.due = due;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &fields);
+            Ok(())
+        })
+    }
+
+    pub fn add_checklist_item(&self, story_id: u32, text: &str) -> Result<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(anyhow!("checklist items cannot be empty!"));
+        }
+
+        self.update_checklist(story_id, |checklist| {
+            checklist.push(ChecklistItem::new(text.to_owned()));
+            Ok(())
+        })
+    }
+
+    // Checklist items are addressed by their position in the checklist, first item first.
+    pub fn toggle_checklist_item(&self, story_id: u32, index: usize) -> Result<()> {
+        self.update_checklist(story_id, |checklist| {
+            let item = checklist
+                .get_mut(index)
+                .ok_or_else(|| anyhow!("could not find checklist item {}!", index + 1))?;
+            item.done = !item.done;
+            Ok(())
+        })
+    }
+
+    pub fn remove_checklist_item(&self, story_id: u32, index: usize) -> Result<()> {
+        self.update_checklist(story_id, |checklist| {
+            if index >= checklist.len() {
+                return Err(anyhow!("could not find checklist item {}!", index + 1));
+            }
+            checklist.remove(index);
+            Ok(())
+        })
+    }
+
+    // The item ends up at position `to`, shifting the items in between.
+    pub fn move_checklist_item(&self, story_id: u32, from: usize, to: usize) -> Result<()> {
+        self.update_checklist(story_id, |checklist| {
+            if from >= checklist.len() {
+                return Err(anyhow!("could not find checklist item {}!", from + 1));
+            }
+            if to >= checklist.len() {
+                return Err(anyhow!(
+                    "cannot move an item to position {} of a {} item checklist!",
+                    to + 1,
+                    checklist.len()
+                ));
+            }
+            let item = checklist.remove(from);
+            checklist.insert(to, item);
+            Ok(())
+        })
+    }
+
+    fn update_checklist(
+        &self,
+        story_id: u32,
+        update: impl FnOnce(&mut Vec<ChecklistItem>) -> Result<()>,
+    ) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            update(&mut story.checklist)?;
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &["checklist"]);
+            Ok(())
+        })
+    }
+
+    // `blocker_id` has to be done before `story_id` can be, the stories may be in any epics.
+    pub fn add_blocker(&self, story_id: u32, blocker_id: u32) -> Result<()> {
+        if story_id == blocker_id {
+            return Err(anyhow!("a story cannot block itself!"));
+        }
+
+        self.logged_transaction(|transaction, changes| {
+            let stories = transaction.snapshot()?.stories;
+            let mut story = stories
+                .get(&story_id)
+                .cloned()
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+            if !stories.contains_key(&blocker_id) {
+                return Err(anyhow!("could not find story {} in database!", blocker_id));
+            }
+            if story.blocked_by.contains(&blocker_id) {
+                return Err(anyhow!(
+                    "story {} is already blocked by story {}!",
+                    story_id,
+                    blocker_id
+                ));
+            }
+            if depends_on(&stories, blocker_id, story_id) {
+                return Err(anyhow!(
+                    "story {} already depends on story {}, blocking it would create a cycle!",
+                    blocker_id,
+                    story_id
+                ));
+            }
+
+            story.blocked_by.push(blocker_id);
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &["blocked_by"]);
+            Ok(())
+        })
+    }
+
+    pub fn remove_blocker(&self, story_id: u32, blocker_id: u32) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let index = story
+                .blocked_by
+                .iter()
+                .position(|id| *id == blocker_id)
+                .ok_or_else(|| {
+                    anyhow!("story {} is not blocked by story {}!", story_id, blocker_id)
+                })?;
+            story.blocked_by.remove(index);
+            transaction.update_story(story_id, story)?;
+            changes.edited(ItemKind::Story, story_id, &["blocked_by"]);
+            Ok(())
+        })
+    }
+
+    // What the story is blocked by, then what it blocks, by story id.
+    pub fn get_dependencies(&self, story_id: u32) -> Result<Vec<Dependency>> {
+        let stories = self.read_db()?.stories;
+        let story = stories
+            .get(&story_id)
+            .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+        let mut blocked_stories = stories
+            .iter()
+            .filter(|(_, other)| other.blocked_by.contains(&story_id))
+            .map(|(other_id, _)| *other_id)
+            .collect::<Vec<_>>();
+        blocked_stories.sort();
+
+        Ok(story
+            .blocked_by
+            .iter()
+            .map(|blocker_id| Dependency::BlockedBy(*blocker_id))
+            .chain(blocked_stories.into_iter().map(Dependency::Blocks))
+            .collect())
+    }
+
+    // The stories blocking `story_id` that aren't done yet.
+    pub fn open_blockers(&self, story_id: u32) -> Result<Vec<(u32, Story)>> {
+        let story = self
+            .get_story(story_id)?
+            .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+        let workflow = self.get_workflow()?;
+        let mut blockers = vec![];
+        for blocker_id in story.blocked_by {
+            if let Some(blocker) = self.get_story(blocker_id)? {
+                if !workflow.is_done(&blocker.status) {
+                    blockers.push((blocker_id, blocker));
+                }
+            }
+        }
+        Ok(blockers)
+    }
+
+    pub fn get_sprints(&self) -> Result<HashMap<u32, Sprint>> {
+        self.database.get_sprints()
+    }
+
+    pub fn active_sprint(&self) -> Result<Option<(u32, Sprint)>> {
+        Ok(active_sprint(&self.get_sprints()?))
+    }
+
+    // Starts a new sprint with the stories the previous one left unfinished. Returns the id of
+    // the sprint and the stories carried over.
+    pub fn start_sprint(
+        &self,
+        name: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(u32, Vec<u32>)> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("sprint names can't be empty!"));
+        }
+        if end < start {
+            return Err(anyhow!("a sprint can't end before it starts!"));
+        }
+
+        self.transaction(|transaction| {
+            let sprints = transaction.get_sprints()?;
+            if let Some((_, sprint)) = active_sprint(&sprints) {
+                return Err(anyhow!(
+                    "sprint {:?} is still active, close it first!",
+                    sprint.name
+                ));
+            }
+
+            let mut sprint = Sprint::new(name.to_owned(), start, end);
+            let last_sprint_id = sprints.keys().max().copied();
+            if let Some(last_sprint_id) = last_sprint_id {
+                sprint.stories = unfinished_stories(transaction, &sprints[&last_sprint_id])?;
+            }
+
+            let sprint_id = last_sprint_id.map_or(1, |id| id + 1);
+            let carried_over = sprint.stories.clone();
+            transaction.insert_sprint(sprint_id, sprint)?;
+            Ok((sprint_id, carried_over))
+        })
+    }
+
+    // Closes the active sprint. Returns its id and the unfinished stories the next sprint will
+    // start with.
+    pub fn close_sprint(&self) -> Result<(u32, Vec<u32>)> {
+        self.transaction(|transaction| {
+            let (sprint_id, mut sprint) = active_sprint(&transaction.get_sprints()?)
+                .ok_or_else(|| anyhow!("there is no active sprint!"))?;
+
+            let unfinished = unfinished_stories(transaction, &sprint)?;
+            sprint.closed = true;
+            transaction.update_sprint(sprint_id, sprint)?;
+            Ok((sprint_id, unfinished))
+        })
+    }
+
+    pub fn add_sprint_story(&self, sprint_id: u32, story_id: u32) -> Result<()> {
+        self.update_sprint_stories(sprint_id, |transaction, stories| {
+            if transaction.get_story(story_id)?.is_none() {
+                return Err(anyhow!("could not find story in database!"));
+            }
+            if stories.contains(&story_id) {
+                return Err(anyhow!("story {} is already in the sprint!", story_id));
+            }
+
+            stories.push(story_id);
+            Ok(())
+        })
+    }
+
+    pub fn remove_sprint_story(&self, sprint_id: u32, story_id: u32) -> Result<()> {
+        self.update_sprint_stories(sprint_id, |_, stories| {
+            let index = stories
+                .iter()
+                .position(|id| *id == story_id)
+                .ok_or_else(|| anyhow!("story {} is not in the sprint!", story_id))?;
+
+            stories.remove(index);
+            Ok(())
+        })
+    }
+
+    // Closed sprints are left as they were, so their burndown doesn't change.
+    fn update_sprint_stories(
+        &self,
+        sprint_id: u32,
+        update: impl FnOnce(&dyn Transaction, &mut Vec<u32>) -> Result<()>,
+    ) -> Result<()> {
+        self.transaction(|transaction| {
+            let mut sprint = transaction
+                .get_sprints()?
+                .remove(&sprint_id)
+                .ok_or_else(|| anyhow!("could not find sprint in database!"))?;
+            if sprint.closed {
+                return Err(anyhow!("sprint {:?} is closed!", sprint.name));
+            }
+
+            update(&*transaction, &mut sprint.stories)?;
+            transaction.update_sprint(sprint_id, sprint)
+        })
+    }
+
+    pub fn burndown(&self, sprint_id: u32, today: NaiveDate) -> Result<Burndown> {
+        let db_state = self.read_db()?;
+        let sprint = db_state
+            .sprints
+            .get(&sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint in database!"))?;
+
+        Ok(sprint.burndown(
+            &db_state.stories,
+            &self.get_events()?,
+            &db_state.workflow,
+            today,
+        ))
+    }
+
+    pub fn add_comment(&self, story_id: u32, comment: Comment) -> Result<()> {
+        if comment.body.trim().is_empty() {
+            return Err(anyhow!("comments cannot be empty!"));
+        }
+
+        self.transaction(|transaction| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            story.comments.push(comment);
+            transaction.update_story(story_id, story)
+        })
+    }
+
+    // Comments are matched by author, time and text rather than by their position in the
+    // thread, which shifts whenever someone else adds or deletes one.
+    pub fn delete_comment(&self, story_id: u32, comment: &Comment) -> Result<()> {
+        self.transaction(|transaction| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            let index = story
+                .comments
+                .iter()
+                .position(|other| other == comment)
+                .ok_or_else(|| anyhow!("could not find the comment on story!"))?;
+            story.comments.remove(index);
+            transaction.update_story(story_id, story)
+        })
+    }
+
+    pub fn update_epic_status(&self, epic_id: u32, status: Status) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut epic = transaction
+                .get_epic(epic_id)?
+                .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+
+            transaction
+                .get_workflow()?
+                .check_transition(&epic.status, &status)?;
+
+            let from = std::mem::replace(&mut epic.status, status.clone());
+            transaction.update_epic(epic_id, epic)?;
+            changes.status_changed(ItemKind::Epic, epic_id, from, status);
+            Ok(())
+        })
+    }
+
+    pub fn update_story_status(&self, story_id: u32, status: Status) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let mut story = transaction
+                .get_story(story_id)?
+                .ok_or_else(|| anyhow!("could not find story in database!"))?;
+
+            transaction
+                .get_workflow()?
+                .check_transition(&story.status, &status)?;
+
+            let from = std::mem::replace(&mut story.status, status.clone());
+            transaction.update_story(story_id, story)?;
+            changes.status_changed(ItemKind::Story, story_id, from, status);
+            Ok(())
+        })
+    }
+
+    pub fn get_events(&self) -> Result<Vec<Event>> {
+        self.database.get_events()
+    }
+
+    // Events about the item, oldest first. An epic's history includes the events of the
+    // stories it holds and of stories moved in or out of it.
+    pub fn get_history(&self, item: ItemKind, item_id: u32) -> Result<Vec<Event>> {
+        let stories = match item {
+            ItemKind::Epic => self
+                .get_epic(item_id)?
+                .map(|epic| epic.stories)
+                .unwrap_or_default(),
+            ItemKind::Story => vec![],
+        };
+
+        let events = self.get_events()?;
+        Ok(events
+            .into_iter()
+            .filter(|event| match (item, event.item, &event.change) {
+                (_, event_item, _) if event_item == item && event.item_id == item_id => true,
+                (
+                    ItemKind::Epic,
+                    ItemKind::Story,
+                    Change::Moved {
+                        from_epic_id,
+                        to_epic_id,
+                    },
+                ) => *from_epic_id == item_id || *to_epic_id == item_id,
+                (ItemKind::Epic, ItemKind::Story, _) => stories.contains(&event.item_id),
+                _ => false,
+            })
+            .collect())
+    }
+
+    pub fn check_integrity(&self) -> Result<Vec<Problem>> {
+        Ok(integrity::check(&self.read_db()?))
+    }
+
+    // Returns the problems that were found and fixed.
+    pub fn repair_integrity(&self) -> Result<Vec<Problem>> {
+        self.transaction(|transaction| {
+            let db_state = transaction.snapshot()?;
+
+            let mut repaired = db_state.clone();
+            let problems = integrity::repair(&mut repaired);
+
+            apply_changes(transaction, &db_state, &repaired)?;
+            Ok(problems)
+        })
+    }
+
+    // Puts the items changed by `revision` back the way they were before it.
+    pub fn undo(&self, revision: &Revision) -> Result<()> {
+        self.restore(&revision.after, &revision.before)
+    }
+
+    pub fn redo(&self, revision: &Revision) -> Result<()> {
+        self.restore(&revision.before, &revision.after)
+    }
+
+    // Items changed since `current` was recorded, from another process for instance, are
+    // left alone rather than overwritten.
+    fn restore(&self, current: &Items, target: &Items) -> Result<()> {
+        self.logged_transaction(|transaction, changes| {
+            let db_state = transaction.snapshot()?;
+            if !current.matches(&db_state) {
+                return Err(anyhow!("the board has been changed since!"));
+            }
+
+            let mut restored = db_state.clone();
+            target.apply_to(&mut restored);
+            apply_changes(transaction, &db_state, &restored)?;
+
+            record_restored(changes, ItemKind::Epic, &db_state.epics, &target.epics)?;
+            record_restored(changes, ItemKind::Story, &db_state.stories, &target.stories)
+        })
+    }
+
+    // Runs `operations` and returns what they changed on the board. The revision is taken
+    // inside each transaction, so changes other processes make in between are left out of it.
+    pub fn record_revision<T>(&self, operations: impl FnOnce() -> T) -> (T, Option<Revision>) {
+        *self.recorded.borrow_mut() = Some(vec![]);
+        let output = operations();
+        let revisions = self.recorded.borrow_mut().take().unwrap_or_default();
+
+        (output, Revision::merge(revisions))
+    }
+
+    // Runs `operations` inside a single backend transaction and hands back their result.
+    fn transaction<T>(
+        &self,
+        operations: impl FnOnce(&mut dyn Transaction) -> Result<T>,
+    ) -> Result<T> {
+        self.run_transaction(|transaction| Ok((operations(transaction)?, vec![])))
+    }
+
+    // Like `transaction`, but the changes reported by `operations` are recorded in the
+    // board's history as part of the same transaction.
+    fn logged_transaction<T>(
+        &self,
+        operations: impl FnOnce(&mut dyn Transaction, &mut Changes) -> Result<T>,
+    ) -> Result<T> {
+        self.run_transaction(|transaction| {
+            let mut changes = Changes::default();
+            let output = operations(transaction, &mut changes)?;
+
+            let timestamp = Utc::now();
+            let events = changes
+                .0
+                .into_iter()
+                .map(|(item, item_id, change)| Event {
+                    timestamp,
+                    actor: self.actor.clone(),
+                    item,
+                    item_id,
+                    change,
+                })
+                .collect();
+            Ok((output, events))
+        })
+    }
+
+    fn run_transaction<T>(
+        &self,
+        operations: impl FnOnce(&mut dyn Transaction) -> Result<(T, Vec<Event>)>,
+    ) -> Result<T> {
+        let recording = self.recorded.borrow().is_some();
+        let mut output = None;
+        let mut revision = None;
+
+        self.database.transaction(Box::new(|transaction| {
+            let before = recording.then(|| transaction.snapshot()).transpose()?;
+            let (result, events) = operations(transaction)?;
+            if let Some(before) = before {
+                revision = Revision::between(&before, &transaction.snapshot()?);
+            }
+
+            output = Some(result);
+            Ok(events)
+        }))?;
+
+        // only committed changes are recorded
+        if let (Some(recorded), Some(revision)) = (self.recorded.borrow_mut().as_mut(), revision) {
+            recorded.push(revision);
+        }
+        output.ok_or_else(|| anyhow!("transaction finished without running its operations"))
+    }
+}
+
+// Changes made during a transaction, waiting to be recorded in the history.
+#[derive(Default)]
+struct Changes(Vec<(ItemKind, u32, Change)>);
+
+impl Changes {
+    fn push(&mut self, item: ItemKind, item_id: u32, change: Change) {
+        self.0.push((item, item_id, change));
+    }
+
+    // Edits that didn't change anything are left out.
+    fn edited(&mut self, item: ItemKind, item_id: u32, fields: &[&str]) {
+        if !fields.is_empty() {
+            let fields = fields.iter().map(|field| field.to_string()).collect();
+            self.push(item, item_id, Change::Edited { fields });
+        }
+    }
+
+    fn status_changed(&mut self, item: ItemKind, item_id: u32, from: Status, to: Status) {
+        if from != to {
+            self.push(item, item_id, Change::StatusChanged { from, to });
+        }
+    }
+}
+
+// Records how the epics or stories in `to` differ from their current state in `from`.
+fn record_restored<T: serde::Serialize>(
+    changes: &mut Changes,
+    item: ItemKind,
+    from: &HashMap<u32, T>,
+    to: &HashMap<u32, Option<T>>,
+) -> Result<()> {
+    let mut item_ids = to.keys().copied().collect::<Vec<_>>();
+    item_ids.sort();
+
+    for item_id in item_ids {
+        let old = from.get(&item_id).map(serde_json::to_value).transpose()?;
+        let new = to[&item_id]
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+
+        match (old, new) {
+            (None, Some(new)) => {
+                let name = serde_json::from_value(new["name"].clone())?;
+                changes.push(item, item_id, Change::Created { name });
+            }
+            (Some(old), None) => {
+                let name = serde_json::from_value(old["name"].clone())?;
+                changes.push(item, item_id, Change::Deleted { name });
+            }
+            (Some(old), Some(new)) => {
+                let from_status = serde_json::from_value(old["status"].clone())?;
+                let to_status = serde_json::from_value(new["status"].clone())?;
+                changes.status_changed(item, item_id, from_status, to_status);
+
+                let fields = old
+                    .as_object()
+                    .into_iter()
+                    .flat_map(|fields| fields.keys())
+                    .filter(|field| *field != "status" && old[field] != new[field])
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                changes.edited(item, item_id, &fields);
+            }
+            (None, None) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn changed_fields<'a>(fields: &[(&'a str, bool)]) -> Vec<&'a str> {
+    fields
+        .iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(field, _)| *field)
+        .collect()
+}
+
+// Whether `story_id` waits on `target_id`, directly or through other blockers.
+fn depends_on(stories: &HashMap<u32, Story>, story_id: u32, target_id: u32) -> bool {
+    let mut visited = vec![];
+    let mut pending = vec![story_id];
+
+    while let Some(id) = pending.pop() {
+        if id == target_id {
+            return true;
+        }
+        if visited.contains(&id) {
+            continue;
+        }
+        visited.push(id);
+
+        if let Some(story) = stories.get(&id) {
+            pending.extend(&story.blocked_by);
+        }
+    }
+    false
+}
+
+// Sprints only keep the stories that still exist, closed ones included.
+fn remove_sprint_stories(transaction: &mut dyn Transaction, deleted_ids: &[u32]) -> Result<()> {
+    for (sprint_id, mut sprint) in transaction.get_sprints()? {
+        if sprint.stories.iter().any(|id| deleted_ids.contains(id)) {
+            sprint.stories.retain(|id| !deleted_ids.contains(id));
+            transaction.update_sprint(sprint_id, sprint)?;
+        }
+    }
+    Ok(())
+}
+
+// The sprint that hasn't been closed yet, if any.
+fn active_sprint(sprints: &HashMap<u32, Sprint>) -> Option<(u32, Sprint)> {
+    sprints
+        .iter()
+        .find(|(_, sprint)| !sprint.closed)
+        .map(|(id, sprint)| (*id, sprint.clone()))
+}
+
+// The stories of a sprint that still exist and aren't done yet.
+fn unfinished_stories(transaction: &dyn Transaction, sprint: &Sprint) -> Result<Vec<u32>> {
+    let workflow = transaction.get_workflow()?;
+
+    let mut unfinished = vec![];
+    for story_id in &sprint.stories {
+        if let Some(story) = transaction.get_story(*story_id)? {
+            if !workflow.is_done(&story.status) {
+                unfinished.push(*story_id);
+            }
+        }
+    }
+    Ok(unfinished)
+}
+
+// Unblocks the stories waiting on stories that are about to be deleted.
+fn remove_blockers(
+    transaction: &mut dyn Transaction,
+    changes: &mut Changes,
+    deleted_ids: &[u32],
+) -> Result<()> {
+    let mut stories = transaction
+        .snapshot()?
+        .stories
+        .into_iter()
+        .collect::<Vec<_>>();
+    stories.sort_by_key(|(id, _)| *id);
+
+    for (story_id, mut story) in stories {
+        if deleted_ids.contains(&story_id)
+            || !story.blocked_by.iter().any(|id| deleted_ids.contains(id))
+        {
+            continue;
+        }
+
+        story.blocked_by.retain(|id| !deleted_ids.contains(id));
+        transaction.update_story(story_id, story)?;
+        changes.edited(ItemKind::Story, story_id, &["blocked_by"]);
+    }
+    Ok(())
+}
+
+fn register_person(
+    transaction: &mut dyn Transaction,
+    username: &str,
+    person: Person,
+) -> Result<()> {
+    if username.trim().is_empty() || username.contains(char::is_whitespace) {
+        return Err(anyhow!("invalid username {:?}!", username));
+    }
+    if transaction.get_person(username)?.is_some() {
+        return Err(anyhow!("{} is already registered!", username));
+    }
+    transaction.insert_person(username, person)
+}
+
+// Only people from the registry can be assigned to epics and stories.
+fn check_assignee(transaction: &dyn Transaction, assignee: Option<&str>) -> Result<()> {
+    match assignee {
+        Some(username) if transaction.get_person(username)?.is_none() => {
+            Err(anyhow!("{} is not a registered team member!", username))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn label_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("label names cannot be empty!"));
+    }
+    Ok(name.to_owned())
+}
+
+fn find_label(transaction: &dyn Transaction, name: &str) -> Result<u32> {
+    transaction
+        .get_labels()?
+        .into_iter()
+        .find(|(_, label)| label.matches(name))
+        .map(|(id, _)| id)
+        .ok_or_else(|| anyhow!("could not find label {}!", name.trim()))
+}
+
+fn find_or_create_label(transaction: &mut dyn Transaction, name: &str) -> Result<u32> {
+    let name = label_name(name)?;
+    let labels = transaction.get_labels()?;
+
+    if let Some((id, _)) = labels.iter().find(|(_, label)| label.matches(&name)) {
+        return Ok(*id);
+    }
+
+    let id = labels.keys().max().map_or(1, |id| id + 1);
+    transaction.insert_label(id, Label { name })?;
+    Ok(id)
+}
+
+fn remove_label_id(label_ids: &mut Vec<u32>, label_id: u32, name: &str) -> Result<()> {
+    let index = label_ids
+        .iter()
+        .position(|id| *id == label_id)
+        .ok_or_else(|| anyhow!("item is not labelled {}!", name.trim()))?;
+    label_ids.remove(index);
+    Ok(())
+}
+
+// Returns whether `label_ids` referenced `from` at all.
+fn replace_label_id(label_ids: &mut Vec<u32>, from: u32, into: u32) -> bool {
+    if !label_ids.contains(&from) {
+        return false;
+    }
+
+    label_ids.retain(|id| *id != from);
+    if !label_ids.contains(&into) {
+        label_ids.push(into);
+    }
+    true
+}
+
+pub trait Database {
+    fn snapshot(&self) -> Result<DBState>;
+    fn get_epics(&self) -> Result<HashMap<u32, Epic>>;
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>>;
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>>;
+    fn get_people(&self) -> Result<HashMap<String, Person>>;
+    fn get_labels(&self) -> Result<HashMap<u32, Label>>;
+    fn get_workflow(&self) -> Result<Workflow>;
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>>;
+
+    // Either every change made through the transaction is persisted along with the events
+    // `operations` hand back for the history, or none of them is.
+    fn transaction(&self, operations: TransactionOperations) -> Result<()>;
+
+    // The history is append-only, events are never changed or removed once written.
+    fn get_events(&self) -> Result<Vec<Event>>;
+}
+
+pub type TransactionOperations<'a> =
+    Box<dyn FnOnce(&mut dyn Transaction) -> Result<Vec<Event>> + 'a>;
+
+pub trait Transaction {
+    fn snapshot(&self) -> Result<DBState>;
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>>;
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>>;
+    fn get_person(&self, username: &str) -> Result<Option<Person>>;
+    fn get_labels(&self) -> Result<HashMap<u32, Label>>;
+    fn get_workflow(&self) -> Result<Workflow>;
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>>;
+
+    fn next_item_id(&mut self) -> Result<u32>;
+    fn set_last_item_id(&mut self, last_item_id: u32) -> Result<()>;
+    fn set_workflow(&mut self, workflow: Workflow) -> Result<()>;
+
+    fn insert_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()>;
+    fn update_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()>;
+    fn delete_epic(&mut self, epic_id: u32) -> Result<()>;
+
+    fn insert_story(&mut self, story_id: u32, story: Story) -> Result<()>;
+    fn update_story(&mut self, story_id: u32, story: Story) -> Result<()>;
+    fn delete_story(&mut self, story_id: u32) -> Result<()>;
+
+    fn insert_person(&mut self, username: &str, person: Person) -> Result<()>;
+    fn update_person(&mut self, username: &str, person: Person) -> Result<()>;
+    fn delete_person(&mut self, username: &str) -> Result<()>;
+
+    fn insert_label(&mut self, label_id: u32, label: Label) -> Result<()>;
+    fn update_label(&mut self, label_id: u32, label: Label) -> Result<()>;
+    fn delete_label(&mut self, label_id: u32) -> Result<()>;
+
+    fn insert_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()>;
+    fn update_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()>;
+    fn delete_sprint(&mut self, sprint_id: u32) -> Result<()>;
+}
+
+// Backends that can only load and store the whole board at once.
+pub trait WholeStateDatabase {
+    fn read_db(&self) -> Result<DBState>;
+    fn write_db(&self, db_state: &DBState) -> Result<()>;
+
+    // Appends to the history and returns its previous length, which `truncate_log` takes to
+    // remove the events again.
+    fn log_events(&self, events: &[Event]) -> Result<u64>;
+    fn truncate_log(&self, len: u64) -> Result<()>;
+    fn read_events(&self) -> Result<Vec<Event>>;
+
+    // Guards a read-modify-write cycle against other processes using the same board.
+    // Backends that are not shared between processes don't need to lock anything.
+    fn lock(&self) -> Result<DatabaseLock> {
+        Ok(DatabaseLock::default())
+    }
+}
+
+impl<T: WholeStateDatabase> Database for T {
+    fn snapshot(&self) -> Result<DBState> {
+        self.read_db()
+    }
+
+    fn get_epics(&self) -> Result<HashMap<u32, Epic>> {
+        Ok(self.read_db()?.epics)
+    }
+
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>> {
+        Ok(self.read_db()?.epics.remove(&epic_id))
+    }
+
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>> {
+        Ok(self.read_db()?.stories.remove(&story_id))
+    }
+
+    fn get_people(&self) -> Result<HashMap<String, Person>> {
+        Ok(self.read_db()?.people)
+    }
+
+    fn get_labels(&self) -> Result<HashMap<u32, Label>> {
+        Ok(self.read_db()?.labels)
+    }
+
+    fn get_workflow(&self) -> Result<Workflow> {
+        Ok(self.read_db()?.workflow)
+    }
+
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>> {
+        Ok(self.read_db()?.sprints)
+    }
+
+    // The events are logged before the board is written, both under the same lock, and taken
+    // back out of the log if writing the board fails.
+    fn transaction(&self, operations: TransactionOperations) -> Result<()> {
+        let _lock = self.lock()?;
+        let mut db_state = self.read_db()?;
+
+        let events = operations(&mut db_state)?;
+        if events.is_empty() {
+            return self.write_db(&db_state);
+        }
+
+        let log_len = self.log_events(&events)?;
+        if let Err(error) = self.write_db(&db_state) {
+            self.truncate_log(log_len).with_context(|| {
+                anyhow!("failed to remove the history of a change that was not saved!")
+            })?;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    fn get_events(&self) -> Result<Vec<Event>> {
+        self.read_events()
+    }
+}
+
+// Whole-state backends run their transactions against an in-memory copy of the board
+// which is only written back once every operation succeeded.
+impl Transaction for DBState {
+    fn snapshot(&self) -> Result<DBState> {
+        Ok(self.clone())
+    }
+
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>> {
+        Ok(self.epics.get(&epic_id).cloned())
+    }
+
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>> {
+        Ok(self.stories.get(&story_id).cloned())
+    }
+
+    fn get_person(&self, username: &str) -> Result<Option<Person>> {
+        Ok(self.people.get(username).cloned())
+    }
+
+    fn get_labels(&self) -> Result<HashMap<u32, Label>> {
+        Ok(self.labels.clone())
+    }
+
+    fn get_workflow(&self) -> Result<Workflow> {
+        Ok(self.workflow.clone())
+    }
+
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>> {
+        Ok(self.sprints.clone())
+    }
+
+    fn next_item_id(&mut self) -> Result<u32> {
+        self.last_item_id += 1;
+        Ok(self.last_item_id)
+    }
+
+    fn set_last_item_id(&mut self, last_item_id: u32) -> Result<()> {
+        self.last_item_id = last_item_id;
+        Ok(())
+    }
+
+    fn set_workflow(&mut self, workflow: Workflow) -> Result<()> {
+        self.workflow = workflow;
+        Ok(())
+    }
+
+    fn insert_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()> {
+        if self.epics.contains_key(&epic_id) {
+            return Err(anyhow!("epic {} already exists!", epic_id));
+        }
+        self.epics.insert(epic_id, epic);
+        Ok(())
+    }
+
+    fn update_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()> {
+        *self
+            .epics
+            .get_mut(&epic_id)
+            .ok_or_else(|| anyhow!("could not find epic in database!"))? = epic;
+        Ok(())
+    }
+
+    fn delete_epic(&mut self, epic_id: u32) -> Result<()> {
+        self.epics
+            .remove(&epic_id)
+            .ok_or_else(|| anyhow!("could not find epic in database!"))?;
+        Ok(())
+    }
+
+    fn insert_story(&mut self, story_id: u32, story: Story) -> Result<()> {
+        if self.stories.contains_key(&story_id) {
+            return Err(anyhow!("story {} already exists!", story_id));
+        }
+        self.stories.insert(story_id, story);
+        Ok(())
+    }
+
+    fn update_story(&mut self, story_id: u32, story: Story) -> Result<()> {
+        *self
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| anyhow!("could not find story in database!"))? = story;
+        Ok(())
+    }
+
+    fn delete_story(&mut self, story_id: u32) -> Result<()> {
+        self.stories
+            .remove(&story_id)
+            .ok_or_else(|| anyhow!("could not find story in database!"))?;
+        Ok(())
+    }
+
+    fn insert_person(&mut self, username: &str, person: Person) -> Result<()> {
+        if self.people.contains_key(username) {
+            return Err(anyhow!("{} is already registered!", username));
+        }
+        self.people.insert(username.to_owned(), person);
+        Ok(())
+    }
+
+    fn update_person(&mut self, username: &str, person: Person) -> Result<()> {
+        *self
+            .people
+            .get_mut(username)
+            .ok_or_else(|| anyhow!("could not find {} in database!", username))? = person;
+        Ok(())
+    }
+
+    fn delete_person(&mut self, username: &str) -> Result<()> {
+        self.people
+            .remove(username)
+            .ok_or_else(|| anyhow!("could not find {} in database!", username))?;
+        Ok(())
+    }
+
+    fn insert_label(&mut self, label_id: u32, label: Label) -> Result<()> {
+        if self.labels.contains_key(&label_id) {
+            return Err(anyhow!("label {} already exists!", label_id));
+        }
+        self.labels.insert(label_id, label);
+        Ok(())
+    }
+
+    fn update_label(&mut self, label_id: u32, label: Label) -> Result<()> {
+        *self
+            .labels
+            .get_mut(&label_id)
+            .ok_or_else(|| anyhow!("could not find label in database!"))? = label;
+        Ok(())
+    }
+
+    fn delete_label(&mut self, label_id: u32) -> Result<()> {
+        self.labels
+            .remove(&label_id)
+            .ok_or_else(|| anyhow!("could not find label in database!"))?;
+        Ok(())
+    }
+
+    fn insert_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()> {
+        if self.sprints.contains_key(&sprint_id) {
+            return Err(anyhow!("sprint {} already exists!", sprint_id));
+        }
+        self.sprints.insert(sprint_id, sprint);
+        Ok(())
+    }
+
+    fn update_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()> {
+        *self
+            .sprints
+            .get_mut(&sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint in database!"))? = sprint;
+        Ok(())
+    }
+
+    fn delete_sprint(&mut self, sprint_id: u32) -> Result<()> {
+        self.sprints
+            .remove(&sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint in database!"))?;
+        Ok(())
+    }
+}
+
+// Issues the entity operations that turn `from` into `to`. New people, labels and stories are
+// written before the items referencing them, and removed ones only after nothing references them
+// anymore.
+fn apply_changes(transaction: &mut dyn Transaction, from: &DBState, to: &DBState) -> Result<()> {
+    if from.last_item_id != to.last_item_id {
+        transaction.set_last_item_id(to.last_item_id)?;
+    }
+
+    if from.workflow != to.workflow {
+        transaction.set_workflow(to.workflow.clone())?;
+    }
+
+    for (username, person) in &to.people {
+        match from.people.get(username) {
+            None => transaction.insert_person(username, person.clone())?,
+            Some(old_person) if old_person != person => {
+                transaction.update_person(username, person.clone())?
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (label_id, label) in &to.labels {
+        match from.labels.get(label_id) {
+            None => transaction.insert_label(*label_id, label.clone())?,
+            Some(old_label) if old_label != label => {
+                transaction.update_label(*label_id, label.clone())?
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (story_id, story) in &to.stories {
+        match from.stories.get(story_id) {
+            None => transaction.insert_story(*story_id, story.clone())?,
+            Some(old_story) if old_story != story => {
+                transaction.update_story(*story_id, story.clone())?
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (epic_id, epic) in &to.epics {
+        match from.epics.get(epic_id) {
+            None => transaction.insert_epic(*epic_id, epic.clone())?,
+            Some(old_epic) if old_epic != epic => {
+                transaction.update_epic(*epic_id, epic.clone())?
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (sprint_id, sprint) in &to.sprints {
+        match from.sprints.get(sprint_id) {
+            None => transaction.insert_sprint(*sprint_id, sprint.clone())?,
+            Some(old_sprint) if old_sprint != sprint => {
+                transaction.update_sprint(*sprint_id, sprint.clone())?
+            }
+            Some(_) => {}
+        }
+    }
+
+    for sprint_id in from.sprints.keys() {
+        if !to.sprints.contains_key(sprint_id) {
+            transaction.delete_sprint(*sprint_id)?;
+        }
+    }
+
+    for epic_id in from.epics.keys() {
+        if !to.epics.contains_key(epic_id) {
+            transaction.delete_epic(*epic_id)?;
+        }
+    }
+
+    for story_id in from.stories.keys() {
+        if !to.stories.contains_key(story_id) {
+            transaction.delete_story(*story_id)?;
+        }
+    }
+
+    for username in from.people.keys() {
+        if !to.people.contains_key(username) {
+            transaction.delete_person(username)?;
+        }
+    }
+
+    for label_id in from.labels.keys() {
+        if !to.labels.contains_key(label_id) {
+            transaction.delete_label(*label_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+// The lock is released as soon as this value is dropped.
+#[derive(Default)]
+pub struct DatabaseLock {
+    _lock_file: Option<File>,
+}
+
+impl DatabaseLock {
+    // Takes an exclusive advisory lock on `lock_path`, retrying until `timeout` elapses.
+    // The holder writes its PID into the file so waiting processes can report who has it.
+    fn acquire(lock_path: &str, timeout: Duration) -> Result<Self> {
+        let mut lock_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_path)
+            .with_context(|| format!("failed to open lock file {}", lock_path))?;
+
+        let started = Instant::now();
+        loop {
+            match lock_file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    if started.elapsed() >= timeout {
+                        let holder = fs::read_to_string(lock_path).unwrap_or_default();
+                        let holder = match holder.trim() {
+                            "" => "unknown".to_owned(),
+                            pid => pid.to_owned(),
+                        };
+                        return Err(anyhow!("board is locked by PID {}", holder));
+                    }
+                    thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(TryLockError::Error(error)) => return Err(error.into()),
+            }
+        }
+
+        lock_file.set_len(0)?;
+        write!(lock_file, "{}", process::id())?;
+        lock_file.flush()?;
+
+        Ok(Self {
+            _lock_file: Some(lock_file),
+        })
+    }
+}
+
+const DEFAULT_BACKUP_GENERATIONS: usize = 3;
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+struct JSONFileDatabase {
+    pub file_path: String,
+    pub backup_generations: usize,
+    pub lock_timeout: Duration,
+}
+
+impl JSONFileDatabase {
+    fn new(file_path: String) -> Self {
+        Self {
+            file_path,
+            backup_generations: DEFAULT_BACKUP_GENERATIONS,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+        }
+    }
+
+    fn lock_path(&self) -> String {
+        format!("{}.lock", self.file_path)
+    }
+
+    // The history is kept as JSON lines next to the board, so appending never rewrites it.
+    fn log_path(&self) -> String {
+        format!("{}.log", self.file_path)
+    }
+
+    fn backup_path(&self, generation: usize) -> String {
+        format!("{}.bak.{}", self.file_path, generation)
+    }
+
+    // Shifts every backup one generation back (dropping the oldest one) and copies the
+    // current database file into generation 1.
+    fn rotate_backups(&self) -> Result<()> {
+        if self.backup_generations == 0 || !Path::new(&self.file_path).exists() {
+            return Ok(());
+        }
+
+        for generation in (1..self.backup_generations).rev() {
+            let backup_path = self.backup_path(generation);
+            if Path::new(&backup_path).exists() {
+                fs::rename(&backup_path, self.backup_path(generation + 1))?;
+            }
+        }
+
+        fs::copy(&self.file_path, self.backup_path(1))?;
+        Ok(())
+    }
+
+    // Writes to a temporary file next to the database, flushes it to disk and then renames
+    // it over the database file so a crash never leaves a truncated board behind.
+    fn write_atomically(&self, contents: &[u8]) -> Result<()> {
+        let tmp_path = format!("{}.tmp", self.file_path);
+
+        let mut tmp_file = File::create(&tmp_path)?;
+        tmp_file.write_all(contents)?;
+        tmp_file.sync_all()?;
+
+        fs::rename(&tmp_path, &self.file_path)?;
+
+        #[cfg(unix)]
+        if let Some(parent) = Path::new(&self.file_path).parent() {
+            let parent = if parent.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                parent
+            };
+            File::open(parent)?.sync_all()?;
+        }
+
+        Ok(())
+    }
+
+    fn restore_backup(&self) -> Result<DBState> {
+        for generation in 1..=self.backup_generations {
+            let Ok(backup_content) = fs::read_to_string(self.backup_path(generation)) else {
+                continue;
+            };
+
+            if let Ok(parsed) = parse_board(&backup_content) {
+                // keep the unreadable file around so it can still be inspected by hand
+                if Path::new(&self.file_path).exists() {
+                    fs::copy(&self.file_path, format!("{}.corrupt", self.file_path))?;
+                }
+
+                self.write_atomically(&serialize_board(&parsed)?)?;
+                return Ok(parsed);
+            }
+        }
+
+        Err(anyhow!(
+            "could not find a readable backup for {}",
+            self.file_path
+        ))
+    }
+
+    // Upgrades the board file in place, keeping a copy of the file as it was before.
+    // Returns the schema version the board was upgraded from.
+    fn migrate(&self) -> Result<u32> {
+        let _lock = self.lock()?;
+
+        let db_content = fs::read_to_string(&self.file_path)?;
+        let mut board: serde_json::Value = serde_json::from_str(&db_content)
+            .with_context(|| format!("failed to parse {}", self.file_path))?;
+
+        let from_version = migrations::upgrade(&mut board)?;
+        if from_version == CURRENT_SCHEMA_VERSION {
+            return Ok(from_version);
+        }
+
+        // make sure the upgraded board is readable before replacing the old one
+        serde_json::from_value::<DBState>(board.clone())
+            .with_context(|| format!("failed to upgrade {}", self.file_path))?;
+
+        fs::copy(
+            &self.file_path,
+            format!("{}.schema-v{}.bak", self.file_path, from_version),
+        )?;
+        self.write_atomically(&serde_json::to_vec(&board)?)?;
+        Ok(from_version)
+    }
+}
+
+// Boards written by older versions are upgraded in memory; the file itself is only
+// rewritten on the next write or by `my-jira migrate`.
+fn parse_board(db_content: &str) -> Result<DBState> {
+    let mut board: serde_json::Value = serde_json::from_str(db_content)?;
+    migrations::upgrade(&mut board)?;
+    Ok(serde_json::from_value(board)?)
+}
+
+fn serialize_board(db_state: &DBState) -> Result<Vec<u8>> {
+    let mut board = serde_json::to_value(db_state)?;
+    board[SCHEMA_VERSION_KEY] = CURRENT_SCHEMA_VERSION.into();
+    Ok(serde_json::to_vec(&board)?)
+}
+
+pub fn migrate_json_board(file_path: String) -> Result<(u32, u32)> {
+    let from_version = JSONFileDatabase::new(file_path).migrate()?;
+    Ok((from_version, CURRENT_SCHEMA_VERSION))
+}
+
+impl WholeStateDatabase for JSONFileDatabase {
+    fn read_db(&self) -> Result<DBState> {
+        let db_content = fs::read_to_string(&self.file_path)
+            .with_context(|| format!("failed to read {}", self.file_path))?;
+        let parsed = parse_board(&db_content).with_context(|| {
+            format!(
+                "failed to parse {} (run with --restore-backup to recover the latest backup)",
+                self.file_path
+            )
+        })?;
+        Ok(parsed)
+    }
+
+    fn write_db(&self, db_state: &DBState) -> Result<()> {
+        let contents = serialize_board(db_state)?;
+
+        self.rotate_backups()?;
+        self.write_atomically(&contents)?;
+        Ok(())
+    }
+
+    // A last line left incomplete by a crash is cut off first, so the new events don't get
+    // appended to it.
+    fn log_events(&self, events: &[Event]) -> Result<u64> {
+        let mut lines = vec![];
+        for event in events {
+            serde_json::to_writer(&mut lines, event)?;
+            lines.push(b'\n');
+        }
+
+        let mut log_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(self.log_path())
+            .with_context(|| format!("failed to open {}", self.log_path()))?;
+
+        let mut log_len = log_file.metadata()?.len();
+        if log_len > 0 {
+            let mut last_byte = [0];
+            log_file.seek(SeekFrom::Start(log_len - 1))?;
+            log_file.read_exact(&mut last_byte)?;
+
+            if last_byte[0] != b'\n' {
+                let mut log_content = vec![];
+                log_file.rewind()?;
+                log_file.read_to_end(&mut log_content)?;
+
+                log_len = log_content
+                    .iter()
+                    .rposition(|byte| *byte == b'\n')
+                    .map_or(0, |index| index as u64 + 1);
+                log_file.set_len(log_len)?;
+            }
+        }
+
+        log_file.seek(SeekFrom::Start(log_len))?;
+        log_file.write_all(&lines)?;
+        log_file.sync_all()?;
+        Ok(log_len)
+    }
+
+    fn truncate_log(&self, len: u64) -> Result<()> {
+        let log_file = OpenOptions::new()
+            .write(true)
+            .open(self.log_path())
+            .with_context(|| format!("failed to open {}", self.log_path()))?;
+        log_file.set_len(len)?;
+        log_file.sync_all()?;
+        Ok(())
+    }
+
+    // A board without a log file simply has no history yet.
+    fn read_events(&self) -> Result<Vec<Event>> {
+        let log_content = match fs::read_to_string(self.log_path()) {
+            Ok(log_content) => log_content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => {
+                return Err(error).with_context(|| format!("failed to read {}", self.log_path()))
+            }
+        };
+
+        // a crash while appending can leave the last line incomplete, it never held an event
+        let torn = !log_content.ends_with('\n');
+        let lines = log_content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect_vec();
+
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| match serde_json::from_str(line) {
+                Ok(event) => Some(Ok(event)),
+                Err(_) if torn && index + 1 == lines.len() => None,
+                Err(error) => Some(Err(error).with_context(|| {
+                    format!("failed to parse line {} of {}", index + 1, self.log_path())
+                })),
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> Result<DatabaseLock> {
+        DatabaseLock::acquire(&self.lock_path(), self.lock_timeout)
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use super::*;
+
+    pub struct MockDB {
+        last_written_state: RefCell<DBState>,
+        events: RefCell<Vec<Event>>,
+    }
+
+    impl MockDB {
+        pub fn new() -> Self {
+            Self {
+                last_written_state: RefCell::new(DBState {
+                    last_item_id: 0,
+                    epics: HashMap::new(),
+                    stories: HashMap::new(),
+                    people: HashMap::new(),
+                    labels: HashMap::new(),
+                    workflow: Workflow::default(),
+                    sprints: HashMap::new(),
+                }),
+                events: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl WholeStateDatabase for MockDB {
+        fn read_db(&self) -> Result<DBState> {
+            let state = self.last_written_state.borrow().clone();
+            Ok(state)
+        }
+
+        fn write_db(&self, db_state: &DBState) -> Result<()> {
+            let latest_state = &self.last_written_state;
+            *latest_state.borrow_mut() = db_state.clone();
+            Ok(())
+        }
+
+        fn log_events(&self, events: &[Event]) -> Result<u64> {
+            let mut logged = self.events.borrow_mut();
+            let log_len = logged.len() as u64;
+            logged.extend_from_slice(events);
+            Ok(log_len)
+        }
+
+        fn truncate_log(&self, len: u64) -> Result<()> {
+            self.events.borrow_mut().truncate(len as usize);
+            Ok(())
+        }
+
+        fn read_events(&self) -> Result<Vec<Event>> {
+            Ok(self.events.borrow().clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::MockDB;
+    use super::*;
+    use std::collections::BTreeMap;
+
+    // Every JiraDatabase test runs against each backend. The temporary directory holding the
+    // board file has to outlive the database, so it is kept next to it.
+    struct TestDB {
+        db: JiraDatabase,
+        _dir: Option<tempfile::TempDir>,
+    }
+
+    impl TestDB {
+        fn mock() -> Self {
+            Self {
+                db: JiraDatabase {
+                    database: Box::new(MockDB::new()),
+                    actor: None,
+                    recorded: Default::default(),
+                },
+                _dir: None,
+            }
+        }
+
+        fn json_file() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            fs::write(
+                &file_path,
+                r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#,
+            )
+            .unwrap();
+
+            Self {
+                db: JiraDatabase::new(Backend::Json, file_path, false).unwrap(),
+                _dir: Some(dir),
+            }
+        }
+
+        fn sqlite() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.sqlite").to_str().unwrap().to_owned();
+            JiraDatabase::init(Backend::Sqlite, &file_path).unwrap();
+
+            Self {
+                db: JiraDatabase::new(Backend::Sqlite, file_path, false).unwrap(),
+                _dir: Some(dir),
+            }
+        }
+    }
+
+    impl std::ops::Deref for TestDB {
+        type Target = JiraDatabase;
+
+        fn deref(&self) -> &Self::Target {
+            &self.db
+        }
+    }
+
+    fn repair_integrity_should_fix_a_broken_board(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        // a story no epic points to, and an id counter that went backwards
+        db.database
+            .transaction(Box::new(|transaction| {
+                transaction.insert_story(10, Story::new("lost".to_owned(), "".to_owned()))?;
+                transaction.set_last_item_id(epic_id)?;
+                Ok(vec![])
+            }))
+            .unwrap();
+
+        assert_eq!(db.check_integrity().unwrap().len(), 2);
+        assert_eq!(db.repair_integrity().unwrap().len(), 2);
+        assert!(db.check_integrity().unwrap().is_empty());
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.last_item_id, 11);
+        assert_eq!(db_state.epics[&11].name, integrity::RECOVERED_EPIC_NAME);
+        assert_eq!(db_state.epics[&11].stories, vec![10]);
+    }
+
+    macro_rules! backend_tests {
+        ($($test:ident),* $(,)?) => {
+            mod mock_db {
+                $(
+                    #[test]
+                    fn $test() {
+                        super::$test(super::TestDB::mock());
+                    }
+                )*
+            }
+
+            mod json_file_db {
+                $(
+                    #[test]
+                    fn $test() {
+                        super::$test(super::TestDB::json_file());
+                    }
+                )*
+            }
+
+            mod sqlite_db {
+                $(
+                    #[test]
+                    fn $test() {
+                        super::$test(super::TestDB::sqlite());
+                    }
+                )*
+            }
+        };
+    }
+
+    fn create_epic_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic.clone());
+
+        assert_eq!(result.is_ok(), true);
+
+        let id = result.unwrap();
+        let db_state = db.read_db().unwrap();
+
+        let expected_id = 1;
+
+        assert_eq!(id, expected_id);
+        assert_eq!(db_state.last_item_id, expected_id);
+        assert_eq!(db_state.epics.get(&id), Some(&epic));
+    }
+
+    fn create_story_should_error_if_invalid_epic_id(db: TestDB) {
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let non_existent_epic_id = 999;
+
+        let result = db.create_story(story, non_existent_epic_id);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn create_story_should_not_change_anything_if_invalid_epic_id(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let non_existent_epic_id = 999;
+
+        let result = db.create_story(
+            Story::new("".to_owned(), "".to_owned()),
+            non_existent_epic_id,
+        );
+        assert!(result.is_err());
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.last_item_id, epic_id);
+        assert!(db_state.stories.is_empty());
+    }
+
+    fn create_story_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story.clone(), epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let id = result.unwrap();
+        let db_state = db.read_db().unwrap();
+
+        let expected_id = 2;
+
+        assert_eq!(id, expected_id);
+        assert_eq!(db_state.last_item_id, expected_id);
+        assert_eq!(
+            db_state.epics.get(&epic_id).unwrap().stories.contains(&id),
+            true
+        );
+        assert_eq!(db_state.stories.get(&id), Some(&story));
+    }
+
+    fn delete_epic_should_error_if_invalid_epic_id(db: TestDB) {
+        let non_existent_epic_id = 999;
+
+        let result = db.delete_epic(non_existent_epic_id);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn delete_epic_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story, epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let story_id = result.unwrap();
+
+        let result = db.delete_epic(epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let db_state = db.read_db().unwrap();
+
+        let expected_last_id = 2;
+
+        assert_eq!(db_state.last_item_id, expected_last_id);
+        assert_eq!(db_state.epics.get(&epic_id), None);
+        assert_eq!(db_state.stories.get(&story_id), None);
+    }
+
+    fn delete_story_should_error_if_invalid_epic_id(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story, epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let story_id = result.unwrap();
+
+        let non_existent_epic_id = 999;
+
+        let result = db.delete_story(non_existent_epic_id, story_id);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn delete_story_should_error_if_story_not_found_in_epic(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story, epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let non_existent_story_id = 999;
+
+        let result = db.delete_story(epic_id, non_existent_story_id);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn delete_story_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story, epic_id);
+        assert_eq!(result.is_ok(), true);
+
+        let story_id = result.unwrap();
+
+        let result = db.delete_story(epic_id, story_id);
+        assert_eq!(result.is_ok(), true);
+
+        let db_state = db.read_db().unwrap();
+
+        let expected_last_id = 2;
+
+        assert_eq!(db_state.last_item_id, expected_last_id);
+        assert_eq!(
+            db_state
+                .epics
+                .get(&epic_id)
+                .unwrap()
+                .stories
+                .contains(&story_id),
+            false
+        );
+        assert_eq!(db_state.stories.get(&story_id), None);
+    }
+
+    fn move_story_should_error_if_story_not_found_in_epic(db: TestDB) {
+        let from_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let to_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), to_epic_id)
+            .unwrap();
+
+        assert!(db.move_story(story_id, from_epic_id, to_epic_id).is_err());
+        assert!(db.move_story(story_id, to_epic_id, to_epic_id).is_err());
+        assert_eq!(
+            db.get_epic(to_epic_id).unwrap().unwrap().stories,
+            vec![story_id]
+        );
+    }
+
+    fn move_story_should_not_change_anything_if_invalid_target_epic(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let db_state = db.read_db().unwrap();
+
+        assert!(db.move_story(story_id, epic_id, 999).is_err());
+        assert_eq!(db.read_db().unwrap(), db_state);
+    }
+
+    fn move_story_should_work(db: TestDB) {
+        let from_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let to_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), from_epic_id)
+            .unwrap();
+        let other_story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), to_epic_id)
+            .unwrap();
+
+        db.move_story(story_id, from_epic_id, to_epic_id).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.epics[&from_epic_id].stories.is_empty());
+        assert_eq!(
+            db_state.epics[&to_epic_id].stories,
+            vec![other_story_id, story_id]
+        );
+        assert!(db_state.stories.contains_key(&story_id));
+    }
+
+    fn person(name: &str) -> Person {
+        Person {
+            name: name.to_owned(),
+        }
+    }
+
+    fn add_person_should_reject_duplicates_and_invalid_usernames(db: TestDB) {
+        db.add_person("alice".to_owned(), person("Alice")).unwrap();
+
+        assert!(db.add_person("alice".to_owned(), person("Other")).is_err());
+        assert!(db.add_person("".to_owned(), person("")).is_err());
+        assert!(db.add_person("bob smith".to_owned(), person("")).is_err());
+
+        let people = db.get_people().unwrap();
+        assert_eq!(people.len(), 1);
+        assert_eq!(people["alice"], person("Alice"));
+    }
+
+    fn assign_story_should_require_a_registered_person(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(db
+            .assign_story(story_id, Some("ghost".to_owned()), None)
+            .is_err());
+        assert!(db
+            .assign_epic(epic_id, Some("ghost".to_owned()), None)
+            .is_err());
+        assert!(db.assign_story(999, None, None).is_err());
+    }
+
+    fn assign_story_should_work(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_person("alice".to_owned(), person("Alice")).unwrap();
+
+        db.assign_story(story_id, Some("alice".to_owned()), None)
+            .unwrap();
+        db.assign_epic(epic_id, Some("alice".to_owned()), None)
+            .unwrap();
+
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().assignee.as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            db.get_epic(epic_id).unwrap().unwrap().assignee.as_deref(),
+            Some("alice")
+        );
+
+        db.assign_story(story_id, None, None).unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().assignee, None);
+    }
+
+    fn assign_story_should_register_a_new_person_only_if_assigned(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(db
+            .assign_story(999, Some("bob".to_owned()), Some(person("Bob")))
+            .is_err());
+        assert!(db.get_people().unwrap().is_empty());
+
+        db.assign_story(story_id, Some("bob".to_owned()), Some(person("Bob")))
+            .unwrap();
+        assert_eq!(db.get_people().unwrap()["bob"], person("Bob"));
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().assignee.as_deref(),
+            Some("bob")
+        );
+
+        assert!(db
+            .assign_epic(epic_id, Some("bob".to_owned()), Some(person("Other")))
+            .is_err());
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().assignee, None);
+    }
+
+    fn remove_person_should_unassign_everything(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_person("alice".to_owned(), person("Alice")).unwrap();
+        db.assign_story(story_id, Some("alice".to_owned()), None)
+            .unwrap();
+        db.assign_epic(epic_id, Some("alice".to_owned()), None)
+            .unwrap();
+
+        db.remove_person("alice").unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.people.is_empty());
+        assert_eq!(db_state.epics[&epic_id].assignee, None);
+        assert_eq!(db_state.stories[&story_id].assignee, None);
+        assert!(db.remove_person("alice").is_err());
+    }
+
+    fn add_label_should_reuse_labels_case_insensitively(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.add_epic_label(epic_id, "Backend").unwrap();
+        db.add_story_label(story_id, " backend ").unwrap();
+        db.add_story_label(story_id, "BACKEND").unwrap();
+        assert!(db.add_story_label(story_id, "  ").is_err());
+        assert!(db.add_story_label(999, "backend").is_err());
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.labels.len(), 1);
+        assert_eq!(db_state.labels[&1].name, "Backend");
+        assert_eq!(db_state.epics[&epic_id].labels, vec![1]);
+        assert_eq!(db_state.stories[&story_id].labels, vec![1]);
+    }
+
+    fn remove_label_should_keep_the_label_registered(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.add_epic_label(epic_id, "backend").unwrap();
+
+        db.remove_epic_label(epic_id, "Backend").unwrap();
+        assert!(db.remove_epic_label(epic_id, "backend").is_err());
+        assert!(db.remove_epic_label(epic_id, "frontend").is_err());
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.epics[&epic_id].labels.is_empty());
+        assert_eq!(db_state.labels.len(), 1);
+    }
+
+    fn rename_label_should_reject_taken_names(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.add_epic_label(epic_id, "backend").unwrap();
+        db.add_epic_label(epic_id, "api").unwrap();
+
+        assert!(db.rename_label("api", "Backend").is_err());
+        assert!(db.rename_label("missing", "other").is_err());
+
+        // changing only the case of a label is fine
+        db.rename_label("api", "API").unwrap();
+        db.rename_label("backend", "server").unwrap();
+
+        let labels = db.get_labels().unwrap();
+        assert_eq!(labels[&1].name, "server");
+        assert_eq!(labels[&2].name, "API");
+    }
+
+    fn merge_labels_should_relabel_everything(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_epic_label(epic_id, "backend").unwrap();
+        db.add_epic_label(epic_id, "api").unwrap();
+        db.add_story_label(story_id, "api").unwrap();
+
+        assert!(db.merge_labels("api", "API").is_err());
+
+        db.merge_labels("api", "backend").unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.labels.len(), 1);
+        assert_eq!(db_state.epics[&epic_id].labels, vec![1]);
+        assert_eq!(db_state.stories[&story_id].labels, vec![1]);
+    }
+
+    fn update_epic_should_error_if_invalid_epic_id(db: TestDB) {
+        let result = db.update_epic(999, "".to_owned(), "".to_owned());
+        assert!(result.is_err());
+    }
+
+    fn update_epic_should_keep_id_status_and_stories(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("typo".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_epic_status(epic_id, Status::IN_PROGRESS).unwrap();
+
+        db.update_epic(epic_id, "name".to_owned(), "description".to_owned())
+            .unwrap();
+
+        let epic = db.get_epic(epic_id).unwrap().unwrap();
+        assert_eq!(epic.name, "name");
+        assert_eq!(epic.description, "description");
+        assert_eq!(epic.status, Status::IN_PROGRESS);
+        assert_eq!(epic.stories, vec![story_id]);
+    }
+
+    fn update_story_should_error_if_invalid_story_id(db: TestDB) {
+        let result = db.update_story(999, "".to_owned(), "".to_owned());
+        assert!(result.is_err());
+    }
+
+    fn update_story_should_keep_id_and_status(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("typo".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::RESOLVED).unwrap();
+
+        db.update_story(story_id, "name".to_owned(), "description".to_owned())
+            .unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.name, "name");
+        assert_eq!(story.description, "description");
+        assert_eq!(story.status, Status::RESOLVED);
+    }
+
+    fn update_story_estimate_should_error_if_invalid_story_id(db: TestDB) {
+        let result = db.update_story_estimate(999, Priority::High, Some(3));
+        assert!(result.is_err());
+    }
+
+    fn update_story_estimate_should_work(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.update_story_estimate(story_id, Priority::Critical, Some(8))
+            .unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.priority, Priority::Critical);
+        assert_eq!(story.points, Some(8));
+
+        db.update_story_estimate(story_id, Priority::Low, None)
+            .unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.priority, Priority::Low);
+        assert_eq!(story.points, None);
+    }
+
+    fn set_due_should_set_and_clear_due_dates(db: TestDB) {
+        let due = NaiveDate::from_ymd_opt(2026, 11, 1);
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.set_epic_due(epic_id, due).unwrap();
+        db.set_story_due(story_id, due).unwrap();
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().due, due);
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().due, due);
+
+        db.set_story_due(story_id, None).unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().due, None);
+
+        assert!(db.set_epic_due(999, due).is_err());
+        assert!(db.set_story_due(999, due).is_err());
+    }
+
+    fn checklist_should_add_toggle_move_and_remove_items(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        for text in ["write", "test", "ship"] {
+            db.add_checklist_item(story_id, text).unwrap();
+        }
+        assert!(db.add_checklist_item(story_id, " ").is_err());
+
+        db.toggle_checklist_item(story_id, 1).unwrap();
+        db.move_checklist_item(story_id, 2, 0).unwrap();
+        db.remove_checklist_item(story_id, 1).unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        let texts = story
+            .checklist
+            .iter()
+            .map(|item| item.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["ship", "test"]);
+        assert_eq!(story.checklist_progress(), (1, 2));
+
+        assert!(db.toggle_checklist_item(story_id, 2).is_err());
+        assert!(db.move_checklist_item(story_id, 0, 2).is_err());
+        assert!(db.remove_checklist_item(story_id, 2).is_err());
+        assert!(db.add_checklist_item(999, "step").is_err());
+    }
+
+    fn add_blocker_should_reject_cycles(db: TestDB) {
+        let epic_ids = (0..2)
+            .map(|_| {
+                db.create_epic(Epic::new("".to_owned(), "".to_owned()))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        // stories 3 and 4 in the first epic, 5 in the second
+        for epic_id in [epic_ids[0], epic_ids[0], epic_ids[1]] {
+            db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+        }
+
+        db.add_blocker(4, 3).unwrap();
+        db.add_blocker(5, 4).unwrap();
+        assert_eq!(db.get_story(5).unwrap().unwrap().blocked_by, vec![4]);
+
+        assert!(db.add_blocker(3, 5).is_err());
+        assert!(db.add_blocker(4, 5).is_err());
+        assert!(db.add_blocker(3, 3).is_err());
+        assert!(db.add_blocker(4, 3).is_err());
+        assert!(db.add_blocker(3, 999).is_err());
+
+        db.update_story_status(3, Status::RESOLVED).unwrap();
+        assert!(db.open_blockers(4).unwrap().is_empty());
+        assert_eq!(db.open_blockers(5).unwrap()[0].0, 4);
+
+        db.remove_blocker(5, 4).unwrap();
+        assert!(db.remove_blocker(5, 4).is_err());
+        db.add_blocker(3, 5).unwrap();
+    }
+
+    fn set_workflow_should_enforce_transitions(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let transitions = BTreeMap::from([
+            ("Todo".to_owned(), vec!["Done".to_owned()]),
+            ("Done".to_owned(), vec!["Todo".to_owned()]),
+        ]);
+        let workflow = Workflow::new(&["Todo", "Done"], &["Done"], Some(&transitions)).unwrap();
+
+        // the epic is still OPEN, which the new workflow doesn't have
+        assert!(db.set_workflow(workflow.clone()).is_err());
+        assert_eq!(db.get_workflow().unwrap(), Workflow::default());
+
+        db.delete_epic(epic_id).unwrap();
+        db.set_workflow(workflow.clone()).unwrap();
+        assert_eq!(db.get_workflow().unwrap(), workflow);
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::new("Todo")
+        );
+
+        assert!(db.update_story_status(story_id, Status::CLOSED).is_err());
+        db.update_story_status(story_id, Status::new("Done"))
+            .unwrap();
+        db.update_epic_status(epic_id, Status::new("Done")).unwrap();
+        assert!(db.check_integrity().unwrap().is_empty());
+    }
+
+    fn start_sprint_should_carry_over_unfinished_stories(db: TestDB) {
+        let start = NaiveDate::from_ymd_opt(2026, 11, 2).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 11, 13).unwrap();
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let done_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let unfinished_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(db.start_sprint("Sprint 1", end, start).is_err());
+        let (sprint_id, carried_over) = db.start_sprint("Sprint 1", start, end).unwrap();
+        assert!(carried_over.is_empty());
+        assert!(db.start_sprint("Sprint 2", start, end).is_err());
+
+        db.add_sprint_story(sprint_id, done_id).unwrap();
+        db.add_sprint_story(sprint_id, unfinished_id).unwrap();
+        assert!(db.add_sprint_story(sprint_id, done_id).is_err());
+        db.update_story_status(done_id, Status::RESOLVED).unwrap();
+
+        assert_eq!(db.close_sprint().unwrap(), (sprint_id, vec![unfinished_id]));
+        assert!(db.active_sprint().unwrap().is_none());
+        assert!(db.add_sprint_story(sprint_id, unfinished_id).is_err());
+
+        let (next_sprint_id, carried_over) = db.start_sprint("Sprint 2", start, end).unwrap();
+        assert_eq!(next_sprint_id, sprint_id + 1);
+        assert_eq!(carried_over, vec![unfinished_id]);
+
+        // deleted stories leave every sprint
+        db.delete_story(epic_id, unfinished_id).unwrap();
+        assert!(db.get_sprints().unwrap()[&next_sprint_id]
+            .stories
+            .is_empty());
+        assert!(db.check_integrity().unwrap().is_empty());
+    }
+
+    fn delete_should_remove_dependencies_on_deleted_stories(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let other_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let blocker_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let other_blocker_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), other_epic_id)
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), other_epic_id)
+            .unwrap();
+        db.add_blocker(story_id, blocker_id).unwrap();
+        db.add_blocker(story_id, other_blocker_id).unwrap();
+
+        db.delete_story(other_epic_id, other_blocker_id).unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().blocked_by,
+            vec![blocker_id]
+        );
+
+        db.delete_epic(epic_id).unwrap();
+        assert!(db
+            .get_story(story_id)
+            .unwrap()
+            .unwrap()
+            .blocked_by
+            .is_empty());
+        assert!(db.check_integrity().unwrap().is_empty());
+    }
+
+    fn add_comment_should_append_to_the_thread(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let first = Comment::new("alice".to_owned(), "first".to_owned());
+        let second = Comment::new("bob".to_owned(), "second".to_owned());
+        db.add_comment(story_id, first.clone()).unwrap();
+        db.add_comment(story_id, second.clone()).unwrap();
+
+        assert!(db
+            .add_comment(story_id, Comment::new("alice".to_owned(), " ".to_owned()))
+            .is_err());
+        assert!(db.add_comment(999, first.clone()).is_err());
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.comments, vec![first, second]);
+    }
+
+    fn delete_comment_should_remove_only_that_comment(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        for body in ["first", "second", "third"] {
+            db.add_comment(story_id, Comment::new("alice".to_owned(), body.to_owned()))
+                .unwrap();
+        }
+
+        let comments = db.get_story(story_id).unwrap().unwrap().comments;
+
+        // deleting the first comment shifts the others, the third one is still found
+        db.delete_comment(story_id, &comments[0]).unwrap();
+        db.delete_comment(story_id, &comments[2]).unwrap();
+        assert!(db.delete_comment(story_id, &comments[0]).is_err());
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.comments, vec![comments[1].clone()]);
+    }
+
+    fn history_should_record_changes_with_their_actor(mut db: TestDB) {
+        db.db.actor = Some("alice".to_owned());
+
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let other_epic_id = db
+            .create_epic(Epic::new("other".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::IN_PROGRESS)
+            .unwrap();
+        // setting the same status again isn't a change
+        db.update_story_status(story_id, Status::IN_PROGRESS)
+            .unwrap();
+        db.update_story(story_id, "renamed".to_owned(), "".to_owned())
+            .unwrap();
+        db.move_story(story_id, epic_id, other_epic_id).unwrap();
+        db.delete_epic(other_epic_id).unwrap();
+
+        let events = db.get_events().unwrap();
+        let changes = events
+            .iter()
+            .map(|event| (event.item, event.item_id, event.change.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    ItemKind::Epic,
+                    epic_id,
+                    Change::Created {
+                        name: "epic".to_owned()
+                    }
+                ),
+                (
+                    ItemKind::Epic,
+                    other_epic_id,
+                    Change::Created {
+                        name: "other".to_owned()
+                    }
+                ),
+                (
+                    ItemKind::Story,
+                    story_id,
+                    Change::Created {
+                        name: "story".to_owned()
+                    }
+                ),
+                (
+                    ItemKind::Story,
+                    story_id,
+                    Change::StatusChanged {
+                        from: Status::OPEN,
+                        to: Status::IN_PROGRESS
+                    }
+                ),
+                (
+                    ItemKind::Story,
+                    story_id,
+                    Change::Edited {
+                        fields: vec!["name".to_owned()]
+                    }
+                ),
+                (
+                    ItemKind::Story,
+                    story_id,
+                    Change::Moved {
+                        from_epic_id: epic_id,
+                        to_epic_id: other_epic_id
+                    }
+                ),
+                (
+                    ItemKind::Story,
+                    story_id,
+                    Change::Deleted {
+                        name: "renamed".to_owned()
+                    }
+                ),
+                (
+                    ItemKind::Epic,
+                    other_epic_id,
+                    Change::Deleted {
+                        name: "other".to_owned()
+                    }
+                ),
+            ]
+        );
+        assert!(events
+            .iter()
+            .all(|event| event.actor.as_deref() == Some("alice")));
+
+        // the epic keeps track of the story that moved out of it
+        assert_eq!(db.get_history(ItemKind::Epic, epic_id).unwrap().len(), 2);
+        assert_eq!(db.get_history(ItemKind::Story, story_id).unwrap().len(), 5);
+    }
+
+    fn history_should_not_record_failed_changes(db: TestDB) {
+        assert!(db.update_story_status(999, Status::CLOSED).is_err());
+        assert!(db
+            .create_story(Story::new("".to_owned(), "".to_owned()), 999)
+            .is_err());
+
+        assert!(db.get_events().unwrap().is_empty());
+    }
+
+    fn undo_should_restore_a_deleted_epic_with_its_stories(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_story_label(story_id, "backend").unwrap();
+        // a dependency from another epic is restored along with the story it points at
+        let other_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let blocked_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), other_epic_id)
+            .unwrap();
+        db.add_blocker(blocked_id, story_id).unwrap();
+        let before = db.read_db().unwrap();
+
+        db.delete_epic(epic_id).unwrap();
+        let revision = Revision::between(&before, &db.read_db().unwrap()).unwrap();
+
+        db.undo(&revision).unwrap();
+        assert_eq!(db.read_db().unwrap(), before);
+        assert!(matches!(
+            db.get_history(ItemKind::Story, story_id)
+                .unwrap()
+                .last()
+                .unwrap()
+                .change,
+            Change::Created { .. }
+        ));
+
+        // ids are never reused, even for items brought back
+        let new_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        assert!(new_epic_id > story_id);
+
+        db.redo(&revision).unwrap();
+        assert!(db.get_epic(epic_id).unwrap().is_none());
+        assert!(db.get_story(story_id).unwrap().is_none());
+    }
+
+    fn undo_should_not_overwrite_later_changes(db: TestDB) {
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let before = db.read_db().unwrap();
+
+        db.update_epic_status(epic_id, Status::IN_PROGRESS).unwrap();
+        let revision = Revision::between(&before, &db.read_db().unwrap()).unwrap();
+        db.update_epic_status(epic_id, Status::CLOSED).unwrap();
+
+        assert!(db.undo(&revision).is_err());
+        assert_eq!(
+            db.get_epic(epic_id).unwrap().unwrap().status,
+            Status::CLOSED
+        );
+    }
+
+    fn update_epic_status_should_error_if_invalid_epic_id(db: TestDB) {
+        let non_existent_epic_id = 999;
+
+        let result = db.update_epic_status(non_existent_epic_id, Status::CLOSED);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn update_epic_status_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+
+        assert_eq!(result.is_ok(), true);
+
+        let epic_id = result.unwrap();
+
+        let result = db.update_epic_status(epic_id, Status::CLOSED);
+
+        assert_eq!(result.is_ok(), true);
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.epics.get(&epic_id).unwrap().status, Status::CLOSED);
+    }
+
+    fn update_story_status_should_error_if_invalid_story_id(db: TestDB) {
+        let non_existent_story_id = 999;
+
+        let result = db.update_story_status(non_existent_story_id, Status::CLOSED);
+        assert_eq!(result.is_err(), true);
+    }
+
+    fn update_story_status_should_work(db: TestDB) {
+        let epic = Epic::new("".to_owned(), "".to_owned());
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let result = db.create_epic(epic);
+
+        let epic_id = result.unwrap();
+
+        let result = db.create_story(story, epic_id);
+
+        let story_id = result.unwrap();
+
+        let result = db.update_story_status(story_id, Status::CLOSED);
+
+        assert_eq!(result.is_ok(), true);
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(
+            db_state.stories.get(&story_id).unwrap().status,
+            Status::CLOSED
+        );
+    }
+
+    backend_tests!(
+        create_epic_should_work,
+        create_story_should_error_if_invalid_epic_id,
+        create_story_should_not_change_anything_if_invalid_epic_id,
+        create_story_should_work,
+        delete_epic_should_error_if_invalid_epic_id,
+        delete_epic_should_work,
+        delete_story_should_error_if_invalid_epic_id,
+        delete_story_should_error_if_story_not_found_in_epic,
+        delete_story_should_work,
+        move_story_should_error_if_story_not_found_in_epic,
+        move_story_should_not_change_anything_if_invalid_target_epic,
+        move_story_should_work,
+        add_person_should_reject_duplicates_and_invalid_usernames,
+        assign_story_should_require_a_registered_person,
+        assign_story_should_work,
+        assign_story_should_register_a_new_person_only_if_assigned,
+        remove_person_should_unassign_everything,
+        add_label_should_reuse_labels_case_insensitively,
+        remove_label_should_keep_the_label_registered,
+        rename_label_should_reject_taken_names,
+        merge_labels_should_relabel_everything,
+        update_epic_should_error_if_invalid_epic_id,
+        update_epic_should_keep_id_status_and_stories,
+        update_story_should_error_if_invalid_story_id,
+        update_story_should_keep_id_and_status,
+        update_story_estimate_should_error_if_invalid_story_id,
+        update_story_estimate_should_work,
+        set_due_should_set_and_clear_due_dates,
+        checklist_should_add_toggle_move_and_remove_items,
+        add_blocker_should_reject_cycles,
+        set_workflow_should_enforce_transitions,
+        start_sprint_should_carry_over_unfinished_stories,
+        delete_should_remove_dependencies_on_deleted_stories,
+        add_comment_should_append_to_the_thread,
+        delete_comment_should_remove_only_that_comment,
+        history_should_record_changes_with_their_actor,
+        history_should_not_record_failed_changes,
+        undo_should_restore_a_deleted_epic_with_its_stories,
+        undo_should_not_overwrite_later_changes,
+        update_epic_status_should_error_if_invalid_epic_id,
+        update_epic_status_should_work,
+        update_story_status_should_error_if_invalid_story_id,
+        update_story_status_should_work,
+        repair_integrity_should_fix_a_broken_board,
+    );
+
+    mod database {
+        use std::collections::HashMap;
+        use std::io::Write;
+
+        use super::*;
+
+        #[test]
+        fn read_db_should_fail_with_invalid_path() {
+            let db = JSONFileDatabase::new("INVALID_PATH".to_owned());
+            assert_eq!(db.read_db().is_err(), true);
+        }
+
+        #[test]
+        fn new_should_fail_for_a_board_that_does_not_exist() {
+            let dir = tempfile::tempdir().unwrap();
+
+            for (backend, file_name) in [(Backend::Json, "db.json"), (Backend::Sqlite, "db.sqlite")]
+            {
+                let file_path = dir.path().join(file_name).to_str().unwrap().to_owned();
+
+                assert!(JiraDatabase::new(backend, file_path.clone(), false).is_err());
+                assert!(!Path::new(&file_path).exists());
+                assert!(!Path::new(&format!("{}.lock", file_path)).exists());
+            }
+        }
+
+        #[test]
+        fn init_should_start_an_empty_board_once() {
+            let dir = tempfile::tempdir().unwrap();
+
+            for (backend, file_name) in [
+                (Backend::Json, "new/db.json"),
+                (Backend::Sqlite, "db.sqlite"),
+            ] {
+                let file_path = dir.path().join(file_name).to_str().unwrap().to_owned();
+
+                JiraDatabase::init(backend, &file_path).unwrap();
+                let db = JiraDatabase::new(backend, file_path.clone(), false).unwrap();
+                assert_eq!(db.read_db().unwrap(), DBState::default());
+
+                assert!(JiraDatabase::init(backend, &file_path).is_err());
+            }
+
+            let board: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(dir.path().join("new/db.json")).unwrap())
+                    .unwrap();
+            assert_eq!(board[SCHEMA_VERSION_KEY], CURRENT_SCHEMA_VERSION);
+        }
+
+        fn created_event(name: &str) -> Event {
+            Event {
+                timestamp: Utc::now(),
+                actor: None,
+                item: ItemKind::Epic,
+                item_id: 1,
+                change: Change::Created {
+                    name: name.to_owned(),
+                },
+            }
+        }
+
+        #[test]
+        fn read_events_should_skip_a_torn_last_line() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path);
+
+            let first = created_event("first");
+            db.log_events(std::slice::from_ref(&first)).unwrap();
+            let mut log_file = OpenOptions::new().append(true).open(db.log_path()).unwrap();
+            write!(log_file, r#"{{"timestamp":"2026-"#).unwrap();
+
+            assert_eq!(db.read_events().unwrap(), vec![first]);
+        }
+
+        #[test]
+        fn read_events_should_fail_with_a_broken_line_before_the_last() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path);
+
+            fs::write(db.log_path(), "not an event\n").unwrap();
+
+            assert!(db.read_events().is_err());
+        }
+
+        #[test]
+        fn log_events_should_cut_off_a_torn_last_line() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path);
+
+            let first = created_event("first");
+            let second = created_event("second");
+            db.log_events(std::slice::from_ref(&first)).unwrap();
+            let log_len = fs::metadata(db.log_path()).unwrap().len();
+            let mut log_file = OpenOptions::new().append(true).open(db.log_path()).unwrap();
+            write!(log_file, r#"{{"timestamp":"2026-"#).unwrap();
+
+            assert_eq!(
+                db.log_events(std::slice::from_ref(&second)).unwrap(),
+                log_len
+            );
+            assert_eq!(db.read_events().unwrap(), vec![first, second]);
+        }
+
+        #[test]
+        fn transaction_should_take_back_events_when_the_board_cannot_be_written() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path.clone());
+            db.write_db(&DBState::default()).unwrap();
+            db.log_events(&[created_event("first")]).unwrap();
+
+            // the board is written through a temporary file, which can't replace a directory
+            fs::create_dir(format!("{}.tmp", file_path)).unwrap();
+            let result = db.transaction(Box::new(|transaction| {
+                transaction.next_item_id()?;
+                Ok(vec![created_event("second")])
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(db.read_events().unwrap().len(), 1);
+            assert_eq!(db.read_db().unwrap().last_item_id, 0);
+        }
+
+        #[test]
+        fn read_db_should_fail_with_invalid_json() {
+            let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+
+            let file_contents = r#"{ "last_item_id": 0 epics: {} stories {} }"#;
+            write!(tmpfile, "{}", file_contents).unwrap();
+
+            let db = JSONFileDatabase::new(
+                tmpfile
+                    .path()
+                    .to_str()
+                    .expect("failed to convert tmpfile path to str")
+                    .to_string(),
+            );
+
+            let result = db.read_db();
+
+            assert_eq!(result.is_err(), true);
+        }
+
+        #[test]
+        fn read_db_should_parse_json_file() {
+            let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+
+            let file_contents = r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#;
+            write!(tmpfile, "{}", file_contents).unwrap();
+
+            let db = JSONFileDatabase::new(
+                tmpfile
+                    .path()
+                    .to_str()
+                    .expect("failed to convert tmpfile path to str")
+                    .to_string(),
+            );
+
+            let result = db.read_db();
+
+            assert_eq!(result.is_ok(), true);
+        }
+
+        #[test]
+        fn write_db_should_work() {
+            let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+
+            let file_contents = r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#;
+            write!(tmpfile, "{}", file_contents).unwrap();
+
+            let db = JSONFileDatabase::new(
+                tmpfile
+                    .path()
+                    .to_str()
+                    .expect("failed to convert tmpfile path to str")
+                    .to_string(),
+            );
+
+            let story = Story {
+                name: "epic 1".to_owned(),
+                description: "epic 1".to_owned(),
+                status: Status::OPEN,
+                assignee: None,
+                priority: Priority::High,
+                points: Some(3),
+                labels: vec![],
+                comments: vec![],
+                due: None,
+                checklist: vec![ChecklistItem::new("step".to_owned())],
+                blocked_by: vec![],
+            };
+            let epic = Epic {
+                name: "epic 1".to_owned(),
+                description: "epic 1".to_owned(),
+                status: Status::OPEN,
+                stories: vec![2],
+                assignee: None,
+                labels: vec![],
+                due: NaiveDate::from_ymd_opt(2026, 11, 1),
+            };
+
+            let mut stories = HashMap::new();
+            stories.insert(2, story);
+
+            let mut epics = HashMap::new();
+            epics.insert(1, epic);
+
+            let state = DBState {
+                last_item_id: 2,
+                epics,
+                stories,
+                people: HashMap::new(),
+                labels: HashMap::new(),
+                workflow: Workflow::default(),
+                sprints: HashMap::new(),
+            };
+
+            let write_result = db.write_db(&state);
+            let read_result = db.read_db().unwrap();
+
+            assert_eq!(write_result.is_ok(), true);
+            assert_eq!(read_result, state);
+        }
+
+        #[test]
+        fn write_db_should_store_schema_version() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path.clone());
+
+            db.write_db(&state_with_last_item_id(1)).unwrap();
+
+            let board: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&file_path).unwrap()).unwrap();
+            assert_eq!(
+                migrations::schema_version(&board).unwrap(),
+                CURRENT_SCHEMA_VERSION
+            );
+        }
+
+        #[test]
+        fn read_db_should_fail_for_newer_schema_version() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            fs::write(
+                &file_path,
+                format!(
+                    r#"{{ "schema_version": {}, "last_item_id": 0, "epics": {{}}, "stories": {{}} }}"#,
+                    CURRENT_SCHEMA_VERSION + 1
+                ),
+            )
+            .unwrap();
+
+            let db = JSONFileDatabase::new(file_path);
+
+            assert!(db.read_db().is_err());
+        }
+
+        #[test]
+        fn migrate_should_upgrade_board_and_keep_old_version() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let file_contents = r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#;
+            fs::write(&file_path, file_contents).unwrap();
+
+            let (from_version, to_version) = migrate_json_board(file_path.clone()).unwrap();
+
+            assert_eq!(from_version, 0);
+            assert_eq!(to_version, CURRENT_SCHEMA_VERSION);
+
+            let board: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&file_path).unwrap()).unwrap();
+            assert_eq!(
+                migrations::schema_version(&board).unwrap(),
+                CURRENT_SCHEMA_VERSION
+            );
+            assert_eq!(
+                fs::read_to_string(format!("{}.schema-v0.bak", file_path)).unwrap(),
+                file_contents
+            );
+
+            let (from_version, _) = migrate_json_board(file_path.clone()).unwrap();
+            assert_eq!(from_version, CURRENT_SCHEMA_VERSION);
+        }
+
+        fn state_with_last_item_id(last_item_id: u32) -> DBState {
+            DBState {
+                last_item_id,
+                epics: HashMap::new(),
+                stories: HashMap::new(),
+                people: HashMap::new(),
+                labels: HashMap::new(),
+                workflow: Workflow::default(),
+                sprints: HashMap::new(),
+            }
+        }
+
+        #[test]
+        fn write_db_should_not_leave_temp_file_behind() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path.clone());
+
+            db.write_db(&state_with_last_item_id(1)).unwrap();
+
+            assert!(Path::new(&file_path).exists());
+            assert!(!Path::new(&format!("{}.tmp", file_path)).exists());
+        }
+
+        #[test]
+        fn write_db_should_rotate_backups() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase {
+                backup_generations: 2,
+                ..JSONFileDatabase::new(file_path)
+            };
+
+            for last_item_id in 1..=4 {
+                db.write_db(&state_with_last_item_id(last_item_id)).unwrap();
+            }
+
+            let read_backup = |generation: usize| -> DBState {
+                let content = fs::read_to_string(db.backup_path(generation)).unwrap();
+                serde_json::from_str(&content).unwrap()
+            };
+
+            assert_eq!(db.read_db().unwrap().last_item_id, 4);
+            assert_eq!(read_backup(1).last_item_id, 3);
+            assert_eq!(read_backup(2).last_item_id, 2);
+            assert!(!Path::new(&db.backup_path(3)).exists());
+        }
+
+        #[test]
+        fn restore_backup_should_fail_without_backups() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            fs::write(&file_path, "{ corrupted").unwrap();
+
+            let db = JSONFileDatabase::new(file_path);
+
+            assert!(db.restore_backup().is_err());
+        }
+
+        #[test]
+        fn restore_backup_should_recover_latest_readable_backup() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path.clone());
+
+            db.write_db(&state_with_last_item_id(1)).unwrap();
+            db.write_db(&state_with_last_item_id(2)).unwrap();
+            db.write_db(&state_with_last_item_id(3)).unwrap();
+
+            fs::write(&file_path, "{ corrupted").unwrap();
+            fs::write(db.backup_path(1), "{ corrupted").unwrap();
+
+            assert!(db.read_db().is_err());
+
+            let restored = db.restore_backup().unwrap();
+
+            assert_eq!(restored.last_item_id, 1);
+            assert_eq!(db.read_db().unwrap(), restored);
+            assert!(Path::new(&format!("{}.corrupt", file_path)).exists());
+        }
+
+        #[test]
+        fn lock_should_fail_with_holder_pid_while_board_is_locked() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase {
+                lock_timeout: Duration::ZERO,
+                ..JSONFileDatabase::new(file_path.clone())
+            };
+            let other_db = JSONFileDatabase {
+                lock_timeout: Duration::ZERO,
+                ..JSONFileDatabase::new(file_path)
+            };
+
+            let lock = db.lock().unwrap();
+
+            let error = other_db.lock().err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("board is locked by PID {}", process::id())
+            );
+
+            drop(lock);
+
+            assert!(other_db.lock().is_ok());
+        }
+
+        #[test]
+        fn jira_database_mutations_should_fail_while_board_is_locked() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            fs::write(
+                &file_path,
+                r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#,
+            )
+            .unwrap();
+
+            let other_process = JSONFileDatabase::new(file_path.clone());
+            let _lock = other_process.lock().unwrap();
+
+            let db = JiraDatabase {
+                database: Box::new(JSONFileDatabase {
+                    lock_timeout: Duration::ZERO,
+                    ..JSONFileDatabase::new(file_path)
+                }),
+                actor: None,
+                recorded: Default::default(),
+            };
+
+            let result = db.create_epic(Epic::new("".to_owned(), "".to_owned()));
+            assert!(result.is_err());
+            assert_eq!(db.read_db().unwrap().epics.len(), 0);
+        }
+
+        #[test]
+        fn jira_database_new_should_restore_backup_when_asked() {
+            let dir = tempfile::tempdir().unwrap();
+            let file_path = dir.path().join("db.json").to_str().unwrap().to_owned();
+            let db = JSONFileDatabase::new(file_path.clone());
+
+            db.write_db(&state_with_last_item_id(1)).unwrap();
+            db.write_db(&state_with_last_item_id(2)).unwrap();
+            fs::write(&file_path, "{ corrupted").unwrap();
+
+            let jira_db = JiraDatabase::new(Backend::Json, file_path.clone(), false).unwrap();
+            assert!(jira_db.read_db().is_err());
+
+            let jira_db = JiraDatabase::new(Backend::Json, file_path, true).unwrap();
+            assert_eq!(jira_db.read_db().unwrap().last_item_id, 1);
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::models::{DBState, Epic, Label, Person, Sprint, Story};
+
+// The state of some of the items of a board, `None` meaning the item doesn't exist.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Items {
+    pub epics: HashMap<u32, Option<Epic>>,
+    pub stories: HashMap<u32, Option<Story>>,
+    pub people: HashMap<String, Option<Person>>,
+    pub labels: HashMap<u32, Option<Label>>,
+    pub sprints: HashMap<u32, Option<Sprint>>,
+}
+
+// A change made to a board, kept as the items it touched before and after the change.
+// Putting the `before` items back undoes it, putting the `after` items back redoes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub before: Items,
+    pub after: Items,
+}
+
+impl Revision {
+    // Returns `None` when nothing changed between the two states.
+    pub fn between(before: &DBState, after: &DBState) -> Option<Self> {
+        let mut revision = Revision {
+            before: Items::default(),
+            after: Items::default(),
+        };
+
+        diff(
+            &before.epics,
+            &after.epics,
+            &mut revision.before.epics,
+            &mut revision.after.epics,
+        );
+        diff(
+            &before.stories,
+            &after.stories,
+            &mut revision.before.stories,
+            &mut revision.after.stories,
+        );
+        diff(
+            &before.people,
+            &after.people,
+            &mut revision.before.people,
+            &mut revision.after.people,
+        );
+        diff(
+            &before.labels,
+            &after.labels,
+            &mut revision.before.labels,
+            &mut revision.after.labels,
+        );
+        diff(
+            &before.sprints,
+            &after.sprints,
+            &mut revision.before.sprints,
+            &mut revision.after.sprints,
+        );
+
+        (revision.before != Items::default()).then_some(revision)
+    }
+
+    // Combines revisions made one after the other into one, going from the items before the
+    // first of them to the items after the last. Returns `None` when nothing is left changed.
+    pub fn merge(revisions: Vec<Revision>) -> Option<Self> {
+        let mut revisions = revisions.into_iter();
+        let mut merged = revisions.next()?;
+
+        for revision in revisions {
+            merged.before.keep_earlier(revision.before);
+            merged.after.keep_later(revision.after);
+        }
+
+        merged.drop_unchanged();
+        (merged.before != Items::default()).then_some(merged)
+    }
+
+    fn drop_unchanged(&mut self) {
+        drop_unchanged(&mut self.before.epics, &mut self.after.epics);
+        drop_unchanged(&mut self.before.stories, &mut self.after.stories);
+        drop_unchanged(&mut self.before.people, &mut self.after.people);
+        drop_unchanged(&mut self.before.labels, &mut self.after.labels);
+        drop_unchanged(&mut self.before.sprints, &mut self.after.sprints);
+    }
+}
+
+impl Items {
+    // Whether every item is currently in the state recorded here.
+    pub fn matches(&self, db_state: &DBState) -> bool {
+        matches(&self.epics, &db_state.epics)
+            && matches(&self.stories, &db_state.stories)
+            && matches(&self.people, &db_state.people)
+            && matches(&self.labels, &db_state.labels)
+            && matches(&self.sprints, &db_state.sprints)
+    }
+
+    fn keep_earlier(&mut self, later: Items) {
+        keep_earlier(&mut self.epics, later.epics);
+        keep_earlier(&mut self.stories, later.stories);
+        keep_earlier(&mut self.people, later.people);
+        keep_earlier(&mut self.labels, later.labels);
+        keep_earlier(&mut self.sprints, later.sprints);
+    }
+
+    fn keep_later(&mut self, later: Items) {
+        self.epics.extend(later.epics);
+        self.stories.extend(later.stories);
+        self.people.extend(later.people);
+        self.labels.extend(later.labels);
+        self.sprints.extend(later.sprints);
+    }
+
+    // Ids are never handed out twice, so `last_item_id` is left alone even when the items
+    // created by a change are removed again.
+    pub fn apply_to(&self, db_state: &mut DBState) {
+        apply(&self.epics, &mut db_state.epics);
+        apply(&self.stories, &mut db_state.stories);
+        apply(&self.people, &mut db_state.people);
+        apply(&self.labels, &mut db_state.labels);
+        apply(&self.sprints, &mut db_state.sprints);
+
+        let max_id = db_state
+            .epics
+            .keys()
+            .chain(db_state.stories.keys())
+            .max()
+            .copied()
+            .unwrap_or(0);
+        db_state.last_item_id = db_state.last_item_id.max(max_id);
+    }
+}
+
+fn diff<K: Eq + Hash + Clone, V: PartialEq + Clone>(
+    before: &HashMap<K, V>,
+    after: &HashMap<K, V>,
+    before_items: &mut HashMap<K, Option<V>>,
+    after_items: &mut HashMap<K, Option<V>>,
+) {
+    for key in before.keys().chain(after.keys()) {
+        let (old, new) = (before.get(key), after.get(key));
+        if old != new {
+            before_items.insert(key.clone(), old.cloned());
+            after_items.insert(key.clone(), new.cloned());
+        }
+    }
+}
+
+fn keep_earlier<K: Eq + Hash, V>(items: &mut HashMap<K, Option<V>>, later: HashMap<K, Option<V>>) {
+    for (key, item) in later {
+        items.entry(key).or_insert(item);
+    }
+}
+
+// Items changed and then changed back again.
+fn drop_unchanged<K: Eq + Hash, V: PartialEq>(
+    before: &mut HashMap<K, Option<V>>,
+    after: &mut HashMap<K, Option<V>>,
+) {
+    before.retain(|key, item| {
+        let unchanged = after.get(key) == Some(item);
+        if unchanged {
+            after.remove(key);
+        }
+        !unchanged
+    });
+}
+
+fn matches<K: Eq + Hash, V: PartialEq>(items: &HashMap<K, Option<V>>, map: &HashMap<K, V>) -> bool {
+    items
+        .iter()
+        .all(|(key, item)| map.get(key) == item.as_ref())
+}
+
+fn apply<K: Eq + Hash + Clone, V: Clone>(items: &HashMap<K, Option<V>>, map: &mut HashMap<K, V>) {
+    for (key, item) in items {
+        match item {
+            Some(item) => map.insert(key.clone(), item.clone()),
+            None => map.remove(key),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Workflow;
+
+    fn board() -> DBState {
+        DBState {
+            last_item_id: 0,
+            epics: HashMap::new(),
+            stories: HashMap::new(),
+            people: HashMap::new(),
+            labels: HashMap::new(),
+            workflow: Workflow::default(),
+            sprints: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn between_should_only_keep_changed_items() {
+        let mut before = board();
+        before
+            .epics
+            .insert(1, Epic::new("kept".to_owned(), "".to_owned()));
+        before
+            .epics
+            .insert(2, Epic::new("deleted".to_owned(), "".to_owned()));
+
+        let mut after = before.clone();
+        after.epics.remove(&2);
+        after.last_item_id = 3;
+        after
+            .stories
+            .insert(3, Story::new("created".to_owned(), "".to_owned()));
+
+        let revision = Revision::between(&before, &after).unwrap();
+
+        assert_eq!(revision.before.epics.len(), 1);
+        assert_eq!(revision.before.epics[&2], before.epics.get(&2).cloned());
+        assert_eq!(revision.after.epics[&2], None);
+        assert_eq!(revision.before.stories[&3], None);
+        assert!(Revision::between(&before, &before).is_none());
+    }
+
+    #[test]
+    fn applying_before_items_should_undo_the_change() {
+        let mut before = board();
+        let mut epic = Epic::new("".to_owned(), "".to_owned());
+        epic.stories = vec![2];
+        before.last_item_id = 2;
+        before.epics.insert(1, epic);
+        before
+            .stories
+            .insert(2, Story::new("".to_owned(), "".to_owned()));
+
+        let mut after = before.clone();
+        after.epics.clear();
+        after.stories.clear();
+
+        let revision = Revision::between(&before, &after).unwrap();
+        assert!(revision.after.matches(&after));
+        assert!(!revision.before.matches(&after));
+
+        let mut undone = after.clone();
+        revision.before.apply_to(&mut undone);
+        assert_eq!(undone, before);
+
+        let mut redone = undone;
+        revision.after.apply_to(&mut redone);
+        assert_eq!(redone, after);
+    }
+
+    #[test]
+    fn merge_should_span_from_the_first_revision_to_the_last() {
+        let first = board();
+        let mut second = first.clone();
+        second
+            .epics
+            .insert(1, Epic::new("created".to_owned(), "".to_owned()));
+        second
+            .stories
+            .insert(2, Story::new("created".to_owned(), "".to_owned()));
+        let mut third = second.clone();
+        third.epics.get_mut(&1).unwrap().name = "renamed".to_owned();
+        third.stories.clear();
+
+        let revision = Revision::merge(vec![
+            Revision::between(&first, &second).unwrap(),
+            Revision::between(&second, &third).unwrap(),
+        ])
+        .unwrap();
+
+        assert_eq!(Some(revision.clone()), Revision::between(&first, &third));
+        // the story created and deleted again is no change at all
+        assert!(!revision.before.stories.contains_key(&2));
+        assert!(Revision::merge(vec![]).is_none());
+        assert!(Revision::merge(vec![
+            Revision::between(&second, &first).unwrap(),
+            Revision::between(&first, &second).unwrap(),
+        ])
+        .is_none());
+    }
+}
@@ -0,0 +1,1083 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{Database, DatabaseLock, Transaction, TransactionOperations, DEFAULT_LOCK_TIMEOUT};
+use crate::models::{
+    ChecklistItem, Comment, DBState, Epic, Event, Label, Person, Sprint, Status, Story, Workflow,
+};
+
+// SCHEMA[n] upgrades a database from `PRAGMA user_version` n to n + 1. Databases created
+// before the schema was versioned are at version 0 but already have the version 1 tables,
+// which is why the first step only creates what is missing.
+const SCHEMA: &[&str] = &[
+    "
+    CREATE TABLE IF NOT EXISTS metadata (
+        key   TEXT PRIMARY KEY,
+        value INTEGER NOT NULL
+    );
+    INSERT OR IGNORE INTO metadata (key, value) VALUES ('last_item_id', 0);
+
+    CREATE TABLE IF NOT EXISTS epics (
+        id          INTEGER PRIMARY KEY,
+        name        TEXT NOT NULL,
+        description TEXT NOT NULL,
+        status      TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS stories (
+        id          INTEGER PRIMARY KEY,
+        name        TEXT NOT NULL,
+        description TEXT NOT NULL,
+        status      TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS epic_stories (
+        epic_id  INTEGER NOT NULL REFERENCES epics (id) ON DELETE CASCADE,
+        story_id INTEGER NOT NULL REFERENCES stories (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (epic_id, story_id)
+    );
+",
+    "
+    CREATE TABLE people (
+        username TEXT PRIMARY KEY,
+        name     TEXT NOT NULL
+    );
+
+    ALTER TABLE epics ADD COLUMN assignee TEXT REFERENCES people (username);
+    ALTER TABLE stories ADD COLUMN assignee TEXT REFERENCES people (username);
+",
+    "
+    ALTER TABLE stories ADD COLUMN priority TEXT NOT NULL DEFAULT 'Medium';
+    ALTER TABLE stories ADD COLUMN points INTEGER;
+",
+    "
+    CREATE TABLE labels (
+        id   INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE COLLATE NOCASE
+    );
+
+    CREATE TABLE epic_labels (
+        epic_id  INTEGER NOT NULL REFERENCES epics (id) ON DELETE CASCADE,
+        label_id INTEGER NOT NULL REFERENCES labels (id),
+        position INTEGER NOT NULL,
+        PRIMARY KEY (epic_id, label_id)
+    );
+
+    CREATE TABLE story_labels (
+        story_id INTEGER NOT NULL REFERENCES stories (id) ON DELETE CASCADE,
+        label_id INTEGER NOT NULL REFERENCES labels (id),
+        position INTEGER NOT NULL,
+        PRIMARY KEY (story_id, label_id)
+    );
+",
+    "
+    CREATE TABLE story_comments (
+        story_id   INTEGER NOT NULL REFERENCES stories (id) ON DELETE CASCADE,
+        position   INTEGER NOT NULL,
+        author     TEXT NOT NULL,
+        body       TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (story_id, position)
+    );
+",
+    "
+    CREATE TABLE events (
+        id        INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp TEXT NOT NULL,
+        actor     TEXT,
+        item      TEXT NOT NULL,
+        item_id   INTEGER NOT NULL,
+        change    TEXT NOT NULL
+    );
+",
+    "
+    ALTER TABLE epics ADD COLUMN due TEXT;
+    ALTER TABLE stories ADD COLUMN due TEXT;
+",
+    "
+    CREATE TABLE story_checklist (
+        story_id INTEGER NOT NULL REFERENCES stories (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        text     TEXT NOT NULL,
+        done     INTEGER NOT NULL,
+        PRIMARY KEY (story_id, position)
+    );
+",
+    "
+    CREATE TABLE story_blockers (
+        story_id   INTEGER NOT NULL REFERENCES stories (id) ON DELETE CASCADE,
+        blocker_id INTEGER NOT NULL
+            REFERENCES stories (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
+        position   INTEGER NOT NULL,
+        PRIMARY KEY (story_id, blocker_id)
+    );
+",
+    "
+    CREATE TABLE workflow_states (
+        name     TEXT PRIMARY KEY,
+        position INTEGER NOT NULL,
+        terminal INTEGER NOT NULL
+    );
+
+    CREATE TABLE workflow_transitions (
+        from_state TEXT NOT NULL REFERENCES workflow_states (name) ON DELETE CASCADE,
+        to_state   TEXT NOT NULL REFERENCES workflow_states (name) ON DELETE CASCADE,
+        PRIMARY KEY (from_state, to_state)
+    );
+
+    INSERT INTO workflow_states (name, position, terminal)
+    VALUES ('Open', 0, 0), ('InProgress', 1, 0), ('Resolved', 2, 1), ('Closed', 3, 1);
+
+    INSERT INTO workflow_transitions (from_state, to_state)
+    SELECT from_state.name, to_state.name
+    FROM workflow_states AS from_state, workflow_states AS to_state
+    WHERE from_state.name != to_state.name;
+",
+    "
+    CREATE TABLE sprints (
+        id         INTEGER PRIMARY KEY,
+        name       TEXT NOT NULL,
+        start_date TEXT NOT NULL,
+        end_date   TEXT NOT NULL,
+        closed     INTEGER NOT NULL
+    );
+
+    CREATE TABLE sprint_stories (
+        sprint_id INTEGER NOT NULL REFERENCES sprints (id) ON DELETE CASCADE,
+        story_id  INTEGER NOT NULL
+            REFERENCES stories (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
+        position  INTEGER NOT NULL,
+        PRIMARY KEY (sprint_id, story_id)
+    );
+",
+];
+
+// A table keeping an ordered list of ids for each owner, like the stories of an epic.
+struct LinkTable {
+    table: &'static str,
+    owner_column: &'static str,
+    item_column: &'static str,
+}
+
+const EPIC_STORIES: LinkTable = LinkTable {
+    table: "epic_stories",
+    owner_column: "epic_id",
+    item_column: "story_id",
+};
+
+const EPIC_LABELS: LinkTable = LinkTable {
+    table: "epic_labels",
+    owner_column: "epic_id",
+    item_column: "label_id",
+};
+
+const STORY_LABELS: LinkTable = LinkTable {
+    table: "story_labels",
+    owner_column: "story_id",
+    item_column: "label_id",
+};
+
+// Blockers may be written after the stories they block, so their references are only checked
+// when the transaction commits.
+const STORY_BLOCKERS: LinkTable = LinkTable {
+    table: "story_blockers",
+    owner_column: "story_id",
+    item_column: "blocker_id",
+};
+
+const SPRINT_STORIES: LinkTable = LinkTable {
+    table: "sprint_stories",
+    owner_column: "sprint_id",
+    item_column: "story_id",
+};
+
+impl LinkTable {
+    fn query(&self, connection: &Connection, owner_id: u32) -> Result<Vec<u32>> {
+        let mut statement = connection.prepare(&format!(
+            "SELECT {} FROM {} WHERE {} = ?1 ORDER BY position",
+            self.item_column, self.table, self.owner_column
+        ))?;
+        let item_ids = statement
+            .query_map(params![owner_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(item_ids)
+    }
+
+    fn replace(&self, connection: &Connection, owner_id: u32, item_ids: &[u32]) -> Result<()> {
+        connection.execute(
+            &format!(
+                "DELETE FROM {} WHERE {} = ?1",
+                self.table, self.owner_column
+            ),
+            params![owner_id],
+        )?;
+
+        for (position, item_id) in item_ids.iter().enumerate() {
+            connection.execute(
+                &format!(
+                    "INSERT INTO {} ({}, {}, position) VALUES (?1, ?2, ?3)",
+                    self.table, self.owner_column, self.item_column
+                ),
+                params![owner_id, item_id, position],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct SqliteDatabase {
+    pub file_path: String,
+    pub lock_timeout: Duration,
+    connection: Connection,
+}
+
+impl SqliteDatabase {
+    pub fn open(file_path: String) -> Result<Self> {
+        let mut connection = Connection::open(&file_path)
+            .with_context(|| format!("failed to open sqlite database {}", file_path))?;
+
+        connection.execute_batch("PRAGMA foreign_keys = ON;")?;
+        upgrade_schema(&mut connection)
+            .with_context(|| format!("failed to upgrade sqlite database {}", file_path))?;
+
+        Ok(Self {
+            file_path,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            connection,
+        })
+    }
+}
+
+fn upgrade_schema(connection: &mut Connection) -> Result<()> {
+    let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+    let version: usize = transaction.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > SCHEMA.len() {
+        return Err(anyhow!(
+            "database uses schema version {} but this version of my-jira only supports up to {}",
+            version,
+            SCHEMA.len()
+        ));
+    }
+
+    for (index, step) in SCHEMA.iter().enumerate().skip(version) {
+        transaction.execute_batch(step)?;
+        transaction.pragma_update(None, "user_version", index + 1)?;
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
+// Enums like statuses and priorities are stored using the same names serde gives them in the
+// JSON board.
+fn enum_to_sql<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_value(value)?
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!("value should serialize to a string"))
+}
+
+fn enum_from_sql<T: DeserializeOwned>(value: String) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(value.clone()))
+        .with_context(|| format!("unknown value {} in database", value))
+}
+
+fn query_epics(connection: &Connection, epic_id: Option<u32>) -> Result<HashMap<u32, Epic>> {
+    let mut statement = connection.prepare(
+        "SELECT id, name, description, status, assignee, due FROM epics
+         WHERE ?1 IS NULL OR id = ?1",
+    )?;
+    let mut rows = statement.query(params![epic_id])?;
+
+    let mut epics = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let id = row.get(0)?;
+        epics.insert(
+            id,
+            Epic {
+                name: row.get(1)?,
+                description: row.get(2)?,
+                status: enum_from_sql(row.get(3)?)?,
+                stories: EPIC_STORIES.query(connection, id)?,
+                assignee: row.get(4)?,
+                labels: EPIC_LABELS.query(connection, id)?,
+                due: row.get(5)?,
+            },
+        );
+    }
+    Ok(epics)
+}
+
+fn query_stories(connection: &Connection, story_id: Option<u32>) -> Result<HashMap<u32, Story>> {
+    let mut statement = connection.prepare(
+        "SELECT id, name, description, status, assignee, priority, points, due
+         FROM stories WHERE ?1 IS NULL OR id = ?1",
+    )?;
+    let mut rows = statement.query(params![story_id])?;
+
+    let mut stories = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let id = row.get(0)?;
+        stories.insert(
+            id,
+            Story {
+                name: row.get(1)?,
+                description: row.get(2)?,
+                status: enum_from_sql(row.get(3)?)?,
+                assignee: row.get(4)?,
+                priority: enum_from_sql(row.get(5)?)?,
+                points: row.get(6)?,
+                labels: STORY_LABELS.query(connection, id)?,
+                comments: query_comments(connection, id)?,
+                due: row.get(7)?,
+                checklist: query_checklist(connection, id)?,
+                blocked_by: STORY_BLOCKERS.query(connection, id)?,
+            },
+        );
+    }
+    Ok(stories)
+}
+
+fn query_comments(connection: &Connection, story_id: u32) -> Result<Vec<Comment>> {
+    let mut statement = connection.prepare(
+        "SELECT author, body, created_at FROM story_comments
+         WHERE story_id = ?1 ORDER BY position",
+    )?;
+    let comments = statement
+        .query_map(params![story_id], |row| {
+            Ok(Comment {
+                author: row.get(0)?,
+                body: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(comments)
+}
+
+fn replace_comments(connection: &Connection, story_id: u32, comments: &[Comment]) -> Result<()> {
+    connection.execute(
+        "DELETE FROM story_comments WHERE story_id = ?1",
+        params![story_id],
+    )?;
+
+    for (position, comment) in comments.iter().enumerate() {
+        connection.execute(
+            "INSERT INTO story_comments (story_id, position, author, body, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                story_id,
+                position,
+                comment.author,
+                comment.body,
+                comment.created_at
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn query_checklist(connection: &Connection, story_id: u32) -> Result<Vec<ChecklistItem>> {
+    let mut statement = connection
+        .prepare("SELECT text, done FROM story_checklist WHERE story_id = ?1 ORDER BY position")?;
+    let checklist = statement
+        .query_map(params![story_id], |row| {
+            Ok(ChecklistItem {
+                text: row.get(0)?,
+                done: row.get(1)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(checklist)
+}
+
+fn replace_checklist(
+    connection: &Connection,
+    story_id: u32,
+    checklist: &[ChecklistItem],
+) -> Result<()> {
+    connection.execute(
+        "DELETE FROM story_checklist WHERE story_id = ?1",
+        params![story_id],
+    )?;
+
+    for (position, item) in checklist.iter().enumerate() {
+        connection.execute(
+            "INSERT INTO story_checklist (story_id, position, text, done)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![story_id, position, item.text, item.done],
+        )?;
+    }
+    Ok(())
+}
+
+fn query_people(connection: &Connection) -> Result<HashMap<String, Person>> {
+    let mut statement = connection.prepare("SELECT username, name FROM people")?;
+    let people = statement
+        .query_map([], |row| Ok((row.get(0)?, Person { name: row.get(1)? })))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(people)
+}
+
+fn query_labels(connection: &Connection) -> Result<HashMap<u32, Label>> {
+    let mut statement = connection.prepare("SELECT id, name FROM labels")?;
+    let labels = statement
+        .query_map([], |row| Ok((row.get(0)?, Label { name: row.get(1)? })))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(labels)
+}
+
+fn query_sprints(connection: &Connection) -> Result<HashMap<u32, Sprint>> {
+    let mut statement =
+        connection.prepare("SELECT id, name, start_date, end_date, closed FROM sprints")?;
+    let mut rows = statement.query([])?;
+
+    let mut sprints = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let id = row.get(0)?;
+        sprints.insert(
+            id,
+            Sprint {
+                name: row.get(1)?,
+                start: row.get(2)?,
+                end: row.get(3)?,
+                stories: SPRINT_STORIES.query(connection, id)?,
+                closed: row.get(4)?,
+            },
+        );
+    }
+    Ok(sprints)
+}
+
+fn query_workflow(connection: &Connection) -> Result<Workflow> {
+    let mut workflow = Workflow {
+        states: vec![],
+        terminal: vec![],
+        transitions: BTreeMap::new(),
+    };
+
+    let mut statement =
+        connection.prepare("SELECT name, terminal FROM workflow_states ORDER BY position")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let state = Status::new(&row.get::<_, String>(0)?);
+        if row.get(1)? {
+            workflow.terminal.push(state.clone());
+        }
+        workflow.states.push(state);
+    }
+
+    // next states are kept in the order the states are defined in
+    let mut statement = connection.prepare(
+        "SELECT from_state, to_state FROM workflow_transitions
+         JOIN workflow_states ON workflow_states.name = to_state
+         ORDER BY position",
+    )?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        workflow
+            .transitions
+            .entry(Status::new(&row.get::<_, String>(0)?))
+            .or_default()
+            .push(Status::new(&row.get::<_, String>(1)?));
+    }
+
+    Ok(workflow)
+}
+
+fn write_workflow(connection: &Connection, workflow: &Workflow) -> Result<()> {
+    connection.execute("DELETE FROM workflow_transitions", [])?;
+    connection.execute("DELETE FROM workflow_states", [])?;
+
+    for (position, state) in workflow.states.iter().enumerate() {
+        connection.execute(
+            "INSERT INTO workflow_states (name, position, terminal) VALUES (?1, ?2, ?3)",
+            params![state.name(), position, workflow.is_done(state)],
+        )?;
+    }
+    for (from, next_states) in &workflow.transitions {
+        for to in next_states {
+            connection.execute(
+                "INSERT INTO workflow_transitions (from_state, to_state) VALUES (?1, ?2)",
+                params![from.name(), to.name()],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn query_snapshot(connection: &Connection) -> Result<DBState> {
+    let last_item_id = connection.query_row(
+        "SELECT value FROM metadata WHERE key = 'last_item_id'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(DBState {
+        last_item_id,
+        epics: query_epics(connection, None)?,
+        stories: query_stories(connection, None)?,
+        people: query_people(connection)?,
+        labels: query_labels(connection)?,
+        workflow: query_workflow(connection)?,
+        sprints: query_sprints(connection)?,
+    })
+}
+
+impl Database for SqliteDatabase {
+    fn snapshot(&self) -> Result<DBState> {
+        query_snapshot(&self.connection)
+    }
+
+    fn get_epics(&self) -> Result<HashMap<u32, Epic>> {
+        query_epics(&self.connection, None)
+    }
+
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>> {
+        Ok(query_epics(&self.connection, Some(epic_id))?.remove(&epic_id))
+    }
+
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>> {
+        Ok(query_stories(&self.connection, Some(story_id))?.remove(&story_id))
+    }
+
+    fn get_people(&self) -> Result<HashMap<String, Person>> {
+        query_people(&self.connection)
+    }
+
+    fn get_labels(&self) -> Result<HashMap<u32, Label>> {
+        query_labels(&self.connection)
+    }
+
+    fn get_workflow(&self) -> Result<Workflow> {
+        query_workflow(&self.connection)
+    }
+
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>> {
+        query_sprints(&self.connection)
+    }
+
+    fn get_events(&self) -> Result<Vec<Event>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT timestamp, actor, item, item_id, change FROM events ORDER BY id")?;
+        let mut rows = statement.query([])?;
+
+        let mut events = vec![];
+        while let Some(row) = rows.next()? {
+            let change: String = row.get(4)?;
+            events.push(Event {
+                timestamp: row.get(0)?,
+                actor: row.get(1)?,
+                item: enum_from_sql(row.get(2)?)?,
+                item_id: row.get(3)?,
+                change: serde_json::from_str(&change)
+                    .with_context(|| format!("unknown change {} in database", change))?,
+            });
+        }
+        Ok(events)
+    }
+
+    fn transaction(&self, operations: TransactionOperations) -> Result<()> {
+        let _lock = DatabaseLock::acquire(&format!("{}.lock", self.file_path), self.lock_timeout)?;
+
+        let mut transaction = SqliteTransaction {
+            transaction: rusqlite::Transaction::new_unchecked(
+                &self.connection,
+                TransactionBehavior::Immediate,
+            )?,
+        };
+
+        // dropping the rusqlite transaction without committing rolls it back
+        let events = operations(&mut transaction)?;
+        insert_events(&transaction.transaction, &events)?;
+
+        transaction.transaction.commit()?;
+        Ok(())
+    }
+}
+
+// Events are not tied to the items they describe, deleted items keep their history.
+// The kind of change and its details are stored as JSON.
+fn insert_events(connection: &Connection, events: &[Event]) -> Result<()> {
+    for event in events {
+        connection.execute(
+            "INSERT INTO events (timestamp, actor, item, item_id, change)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                event.timestamp,
+                event.actor,
+                enum_to_sql(&event.item)?,
+                event.item_id,
+                serde_json::to_string(&event.change)?
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+struct SqliteTransaction<'a> {
+    transaction: rusqlite::Transaction<'a>,
+}
+
+impl Transaction for SqliteTransaction<'_> {
+    fn snapshot(&self) -> Result<DBState> {
+        query_snapshot(&self.transaction)
+    }
+
+    fn get_epic(&self, epic_id: u32) -> Result<Option<Epic>> {
+        Ok(query_epics(&self.transaction, Some(epic_id))?.remove(&epic_id))
+    }
+
+    fn get_story(&self, story_id: u32) -> Result<Option<Story>> {
+        Ok(query_stories(&self.transaction, Some(story_id))?.remove(&story_id))
+    }
+
+    fn get_person(&self, username: &str) -> Result<Option<Person>> {
+        let person = self
+            .transaction
+            .query_row(
+                "SELECT name FROM people WHERE username = ?1",
+                params![username],
+                |row| Ok(Person { name: row.get(0)? }),
+            )
+            .optional()?;
+        Ok(person)
+    }
+
+    fn get_labels(&self) -> Result<HashMap<u32, Label>> {
+        query_labels(&self.transaction)
+    }
+
+    fn get_workflow(&self) -> Result<Workflow> {
+        query_workflow(&self.transaction)
+    }
+
+    fn get_sprints(&self) -> Result<HashMap<u32, Sprint>> {
+        query_sprints(&self.transaction)
+    }
+
+    fn next_item_id(&mut self) -> Result<u32> {
+        let next_id = self.transaction.query_row(
+            "UPDATE metadata SET value = value + 1 WHERE key = 'last_item_id' RETURNING value",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(next_id)
+    }
+
+    fn set_last_item_id(&mut self, last_item_id: u32) -> Result<()> {
+        self.transaction.execute(
+            "UPDATE metadata SET value = ?1 WHERE key = 'last_item_id'",
+            params![last_item_id],
+        )?;
+        Ok(())
+    }
+
+    fn set_workflow(&mut self, workflow: Workflow) -> Result<()> {
+        write_workflow(&self.transaction, &workflow)
+    }
+
+    fn insert_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()> {
+        self.transaction.execute(
+            "INSERT INTO epics (id, name, description, status, assignee, due)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                epic_id,
+                epic.name,
+                epic.description,
+                enum_to_sql(&epic.status)?,
+                epic.assignee,
+                epic.due
+            ],
+        )?;
+        EPIC_STORIES.replace(&self.transaction, epic_id, &epic.stories)?;
+        EPIC_LABELS.replace(&self.transaction, epic_id, &epic.labels)
+    }
+
+    fn update_epic(&mut self, epic_id: u32, epic: Epic) -> Result<()> {
+        let updated = self.transaction.execute(
+            "UPDATE epics SET name = ?2, description = ?3, status = ?4, assignee = ?5, due = ?6
+             WHERE id = ?1",
+            params![
+                epic_id,
+                epic.name,
+                epic.description,
+                enum_to_sql(&epic.status)?,
+                epic.assignee,
+                epic.due
+            ],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("could not find epic in database!"));
+        }
+        EPIC_STORIES.replace(&self.transaction, epic_id, &epic.stories)?;
+        EPIC_LABELS.replace(&self.transaction, epic_id, &epic.labels)
+    }
+
+    fn delete_epic(&mut self, epic_id: u32) -> Result<()> {
+        let deleted = self
+            .transaction
+            .execute("DELETE FROM epics WHERE id = ?1", params![epic_id])?;
+        if deleted == 0 {
+            return Err(anyhow!("could not find epic in database!"));
+        }
+        Ok(())
+    }
+
+    fn insert_story(&mut self, story_id: u32, story: Story) -> Result<()> {
+        self.transaction.execute(
+            "INSERT INTO stories (id, name, description, status, assignee, priority, points, due)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                story_id,
+                story.name,
+                story.description,
+                enum_to_sql(&story.status)?,
+                story.assignee,
+                enum_to_sql(&story.priority)?,
+                story.points,
+                story.due
+            ],
+        )?;
+        STORY_LABELS.replace(&self.transaction, story_id, &story.labels)?;
+        replace_comments(&self.transaction, story_id, &story.comments)?;
+        replace_checklist(&self.transaction, story_id, &story.checklist)?;
+        STORY_BLOCKERS.replace(&self.transaction, story_id, &story.blocked_by)
+    }
+
+    fn update_story(&mut self, story_id: u32, story: Story) -> Result<()> {
+        let updated = self.transaction.execute(
+            "UPDATE stories
+             SET name = ?2, description = ?3, status = ?4, assignee = ?5, priority = ?6,
+                 points = ?7, due = ?8
+             WHERE id = ?1",
+            params![
+                story_id,
+                story.name,
+                story.description,
+                enum_to_sql(&story.status)?,
+                story.assignee,
+                enum_to_sql(&story.priority)?,
+                story.points,
+                story.due
+            ],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("could not find story in database!"));
+        }
+        STORY_LABELS.replace(&self.transaction, story_id, &story.labels)?;
+        replace_comments(&self.transaction, story_id, &story.comments)?;
+        replace_checklist(&self.transaction, story_id, &story.checklist)?;
+        STORY_BLOCKERS.replace(&self.transaction, story_id, &story.blocked_by)
+    }
+
+    fn delete_story(&mut self, story_id: u32) -> Result<()> {
+        let deleted = self
+            .transaction
+            .execute("DELETE FROM stories WHERE id = ?1", params![story_id])?;
+        if deleted == 0 {
+            return Err(anyhow!("could not find story in database!"));
+        }
+        Ok(())
+    }
+
+    fn insert_person(&mut self, username: &str, person: Person) -> Result<()> {
+        self.transaction.execute(
+            "INSERT INTO people (username, name) VALUES (?1, ?2)",
+            params![username, person.name],
+        )?;
+        Ok(())
+    }
+
+    fn update_person(&mut self, username: &str, person: Person) -> Result<()> {
+        let updated = self.transaction.execute(
+            "UPDATE people SET name = ?2 WHERE username = ?1",
+            params![username, person.name],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("could not find {} in database!", username));
+        }
+        Ok(())
+    }
+
+    fn delete_person(&mut self, username: &str) -> Result<()> {
+        let deleted = self
+            .transaction
+            .execute("DELETE FROM people WHERE username = ?1", params![username])?;
+        if deleted == 0 {
+            return Err(anyhow!("could not find {} in database!", username));
+        }
+        Ok(())
+    }
+
+    fn insert_label(&mut self, label_id: u32, label: Label) -> Result<()> {
+        self.transaction.execute(
+            "INSERT INTO labels (id, name) VALUES (?1, ?2)",
+            params![label_id, label.name],
+        )?;
+        Ok(())
+    }
+
+    fn update_label(&mut self, label_id: u32, label: Label) -> Result<()> {
+        let updated = self.transaction.execute(
+            "UPDATE labels SET name = ?2 WHERE id = ?1",
+            params![label_id, label.name],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("could not find label in database!"));
+        }
+        Ok(())
+    }
+
+    fn delete_label(&mut self, label_id: u32) -> Result<()> {
+        let deleted = self
+            .transaction
+            .execute("DELETE FROM labels WHERE id = ?1", params![label_id])?;
+        if deleted == 0 {
+            return Err(anyhow!("could not find label in database!"));
+        }
+        Ok(())
+    }
+
+    fn insert_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()> {
+        self.transaction.execute(
+            "INSERT INTO sprints (id, name, start_date, end_date, closed)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                sprint_id,
+                sprint.name,
+                sprint.start,
+                sprint.end,
+                sprint.closed
+            ],
+        )?;
+        SPRINT_STORIES.replace(&self.transaction, sprint_id, &sprint.stories)
+    }
+
+    fn update_sprint(&mut self, sprint_id: u32, sprint: Sprint) -> Result<()> {
+        let updated = self.transaction.execute(
+            "UPDATE sprints SET name = ?2, start_date = ?3, end_date = ?4, closed = ?5
+             WHERE id = ?1",
+            params![
+                sprint_id,
+                sprint.name,
+                sprint.start,
+                sprint.end,
+                sprint.closed
+            ],
+        )?;
+        if updated == 0 {
+            return Err(anyhow!("could not find sprint in database!"));
+        }
+        SPRINT_STORIES.replace(&self.transaction, sprint_id, &sprint.stories)
+    }
+
+    fn delete_sprint(&mut self, sprint_id: u32) -> Result<()> {
+        let deleted = self
+            .transaction
+            .execute("DELETE FROM sprints WHERE id = ?1", params![sprint_id])?;
+        if deleted == 0 {
+            return Err(anyhow!("could not find sprint in database!"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Change, ItemKind};
+    use chrono::Utc;
+
+    fn open_temp_database() -> (SqliteDatabase, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("db.sqlite").to_str().unwrap().to_owned();
+
+        (SqliteDatabase::open(file_path).unwrap(), dir)
+    }
+
+    #[test]
+    fn open_should_create_an_empty_board() {
+        let (db, _dir) = open_temp_database();
+        let db_state = db.snapshot().unwrap();
+
+        assert_eq!(db_state.last_item_id, 0);
+        assert!(db_state.epics.is_empty());
+        assert!(db_state.stories.is_empty());
+    }
+
+    #[test]
+    fn transaction_should_store_rows_in_tables() {
+        let (db, _dir) = open_temp_database();
+
+        db.transaction(Box::new(|transaction| {
+            let epic_id = transaction.next_item_id()?;
+            let first_story_id = transaction.next_item_id()?;
+            let second_story_id = transaction.next_item_id()?;
+
+            transaction.insert_story(
+                first_story_id,
+                Story::new("story 2".to_owned(), "".to_owned()),
+            )?;
+            transaction.insert_story(
+                second_story_id,
+                Story::new("story 3".to_owned(), "".to_owned()),
+            )?;
+
+            let mut epic = Epic::new("epic 1".to_owned(), "epic 1".to_owned());
+            epic.stories = vec![second_story_id, first_story_id];
+            transaction.insert_epic(epic_id, epic)?;
+            Ok(vec![])
+        }))
+        .unwrap();
+
+        let story_count: u32 = db
+            .connection
+            .query_row("SELECT COUNT(*) FROM stories", [], |row| row.get(0))
+            .unwrap();
+        let status: String = db
+            .connection
+            .query_row("SELECT status FROM epics WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+
+        assert_eq!(story_count, 2);
+        assert_eq!(status, "Open");
+
+        let db_state = db.snapshot().unwrap();
+        assert_eq!(db_state.last_item_id, 3);
+        assert_eq!(db_state.epics[&1].stories, vec![3, 2]);
+        assert_eq!(db.get_story(2).unwrap().unwrap().name, "story 2");
+    }
+
+    #[test]
+    fn transaction_should_roll_back_when_an_operation_fails() {
+        let (db, _dir) = open_temp_database();
+
+        let result = db.transaction(Box::new(|transaction| {
+            let epic_id = transaction.next_item_id()?;
+            transaction.insert_epic(epic_id, Epic::new("".to_owned(), "".to_owned()))?;
+            transaction.update_story(999, Story::new("".to_owned(), "".to_owned()))?;
+            Ok(vec![])
+        }));
+
+        assert!(result.is_err());
+
+        let db_state = db.snapshot().unwrap();
+        assert_eq!(db_state.last_item_id, 0);
+        assert!(db_state.epics.is_empty());
+    }
+
+    #[test]
+    fn transaction_should_roll_back_its_events_when_an_operation_fails() {
+        let (db, _dir) = open_temp_database();
+
+        let result = db.transaction(Box::new(|transaction| {
+            transaction.update_story(999, Story::new("".to_owned(), "".to_owned()))?;
+            Ok(vec![Event {
+                timestamp: Utc::now(),
+                actor: None,
+                item: ItemKind::Story,
+                item_id: 999,
+                change: Change::Edited { fields: vec![] },
+            }])
+        }));
+
+        assert!(result.is_err());
+        assert!(db.get_events().unwrap().is_empty());
+    }
+
+    #[test]
+    fn open_should_upgrade_databases_created_before_versioning() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("db.sqlite").to_str().unwrap().to_owned();
+
+        let connection = Connection::open(&file_path).unwrap();
+        connection.execute_batch(SCHEMA[0]).unwrap();
+        connection
+            .execute(
+                "INSERT INTO stories (id, name, description, status) VALUES (1, 'old', '', 'Open')",
+                [],
+            )
+            .unwrap();
+        drop(connection);
+
+        let db = SqliteDatabase::open(file_path).unwrap();
+
+        let version: usize = db
+            .connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA.len());
+        assert_eq!(db.get_story(1).unwrap().unwrap().assignee, None);
+        assert!(db.get_people().unwrap().is_empty());
+    }
+
+    #[test]
+    fn delete_story_should_delete_its_comments_and_checklist() {
+        let (db, _dir) = open_temp_database();
+
+        db.transaction(Box::new(|transaction| {
+            let mut story = Story::new("".to_owned(), "".to_owned());
+            story.comments = vec![Comment::new("alice".to_owned(), "hi".to_owned())];
+            story.checklist = vec![ChecklistItem::new("step".to_owned())];
+            transaction.insert_story(1, story)?;
+            transaction.delete_story(1)?;
+            Ok(vec![])
+        }))
+        .unwrap();
+
+        let comment_count: u32 = db
+            .connection
+            .query_row("SELECT COUNT(*) FROM story_comments", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(comment_count, 0);
+
+        let checklist_count: u32 = db
+            .connection
+            .query_row("SELECT COUNT(*) FROM story_checklist", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(checklist_count, 0);
+    }
+
+    #[test]
+    fn assignee_should_reference_a_registered_person() {
+        let (db, _dir) = open_temp_database();
+
+        let result = db.transaction(Box::new(|transaction| {
+            let mut story = Story::new("".to_owned(), "".to_owned());
+            story.assignee = Some("ghost".to_owned());
+            transaction.insert_story(1, story)?;
+            Ok(vec![])
+        }));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn update_epic_should_reject_dangling_story_references() {
+        let (db, _dir) = open_temp_database();
+
+        let result = db.transaction(Box::new(|transaction| {
+            let mut epic = Epic::new("".to_owned(), "".to_owned());
+            transaction.insert_epic(1, epic.clone())?;
+
+            epic.stories = vec![999];
+            transaction.update_epic(1, epic)?;
+            Ok(vec![])
+        }));
+
+        assert!(result.is_err());
+        assert!(db.get_epics().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+use std::fmt::{Display, Write};
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::models::{DBState, Epic, PointsSummary, Status, Story};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Md,
+    Csv,
+    Html,
+    Json,
+}
+
+// Which part of the board goes into a report. Empty lists let everything through.
+#[derive(Debug, Default)]
+pub struct ExportFilter {
+    pub epic_ids: Vec<u32>,
+    // stories in other states are left out, along with epics that have none of them
+    pub statuses: Vec<Status>,
+}
+
+struct EpicReport<'a> {
+    id: u32,
+    epic: &'a Epic,
+    progress: Progress,
+    story_reports: Vec<StoryReport<'a>>,
+}
+
+// How far an epic is, counting all of its stories whatever the filter.
+#[derive(Serialize)]
+struct Progress {
+    done: usize,
+    total: usize,
+    points_completed: u32,
+    points_total: u32,
+}
+
+#[derive(Serialize)]
+struct StoryReport<'a> {
+    id: u32,
+    #[serde(flatten)]
+    story: &'a Story,
+}
+
+impl Progress {
+    fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{} stories done ({}%), {}/{} points",
+            self.done,
+            self.total,
+            self.percent(),
+            self.points_completed,
+            self.points_total
+        )
+    }
+}
+
+// Renders the epics of the board with their stories, both sorted by id.
+pub fn export(
+    db_state: &DBState,
+    format: ExportFormat,
+    filter: &ExportFilter,
+    today: NaiveDate,
+) -> Result<String> {
+    let reports = epic_reports(db_state, filter)?;
+
+    Ok(match format {
+        ExportFormat::Md => markdown(&reports, today),
+        ExportFormat::Csv => csv(&reports),
+        ExportFormat::Html => html(&reports, today),
+        ExportFormat::Json => json(&reports)?,
+    })
+}
+
+fn epic_reports<'a>(db_state: &'a DBState, filter: &ExportFilter) -> Result<Vec<EpicReport<'a>>> {
+    if let Some(epic_id) = filter
+        .epic_ids
+        .iter()
+        .find(|epic_id| !db_state.epics.contains_key(epic_id))
+    {
+        return Err(anyhow!("could not find epic {}!", epic_id));
+    }
+    let shown = |status: &Status| filter.statuses.is_empty() || filter.statuses.contains(status);
+
+    let mut reports = vec![];
+    for (id, epic) in db_state.epics.iter().sorted_by_key(|(id, _)| **id) {
+        if !filter.epic_ids.is_empty() && !filter.epic_ids.contains(id) {
+            continue;
+        }
+
+        let stories = epic
+            .stories
+            .iter()
+            .sorted()
+            .filter_map(|story_id| Some((*story_id, db_state.stories.get(story_id)?)))
+            .collect::<Vec<_>>();
+        let points = PointsSummary::from_stories(
+            stories.iter().map(|(_, story)| *story),
+            &db_state.workflow,
+        );
+        let progress = Progress {
+            done: stories
+                .iter()
+                .filter(|(_, story)| db_state.workflow.is_done(&story.status))
+                .count(),
+            total: stories.len(),
+            points_completed: points.completed,
+            points_total: points.total,
+        };
+
+        let story_reports = stories
+            .into_iter()
+            .filter(|(_, story)| shown(&story.status))
+            .map(|(id, story)| StoryReport { id, story })
+            .collect::<Vec<_>>();
+        if !shown(&epic.status) && story_reports.is_empty() {
+            continue;
+        }
+
+        reports.push(EpicReport {
+            id: *id,
+            epic,
+            progress,
+            story_reports,
+        });
+    }
+    Ok(reports)
+}
+
+// The cells of a story row, shared by every table format.
+fn story_cells(story_report: &StoryReport) -> [String; 7] {
+    let story = story_report.story;
+    [
+        story_report.id.to_string(),
+        story.name.clone(),
+        story.status.to_string(),
+        story.priority.to_string(),
+        story
+            .points
+            .map_or_else(|| "-".to_owned(), |points| points.to_string()),
+        story.assignee.clone().unwrap_or_else(|| "-".to_owned()),
+        story
+            .due
+            .map_or_else(|| "-".to_owned(), |due| due.to_string()),
+    ]
+}
+
+const STORY_HEADERS: [&str; 7] = [
+    "id", "name", "status", "priority", "points", "assignee", "due",
+];
+
+fn markdown(reports: &[EpicReport], today: NaiveDate) -> String {
+    // a `|` would end the table cell, and a line break the row
+    let cell = |text: &str| text.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let mut out = String::new();
+
+    writeln!(out, "# Board report").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "Generated on {}.", today).unwrap();
+
+    for report in reports {
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "## {} {} ({})",
+            report.id,
+            cell(&report.epic.name),
+            report.epic.status
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+        if !report.epic.description.is_empty() {
+            writeln!(out, "{}", report.epic.description).unwrap();
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "Progress: {}", report.progress).unwrap();
+
+        if report.story_reports.is_empty() {
+            continue;
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "| {} |", STORY_HEADERS.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(STORY_HEADERS.len())).unwrap();
+        for story_report in &report.story_reports {
+            let cells = story_cells(story_report).map(|text| cell(&text));
+            writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+        }
+    }
+    out
+}
+
+// The epic's own story ids are left out, the stories themselves follow its progress.
+fn json(reports: &[EpicReport]) -> Result<String> {
+    let reports = reports
+        .iter()
+        .map(|report| {
+            serde_json::json!({
+                "id": report.id,
+                "name": report.epic.name,
+                "description": report.epic.description,
+                "status": report.epic.status,
+                "assignee": report.epic.assignee,
+                "labels": report.epic.labels,
+                "due": report.epic.due,
+                "progress": report.progress,
+                "stories": report.story_reports,
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(serde_json::to_string_pretty(&reports)? + "\n")
+}
+
+// One row per story, with the epic repeated on each. Epics without stories to show get a row
+// of their own with the story columns left empty.
+fn csv(reports: &[EpicReport]) -> String {
+    // fields holding a separator, a quote or a line break are quoted, with quotes doubled
+    let field = |text: &str| {
+        if text.contains([',', '"', '\r', '\n']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_owned()
+        }
+    };
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "epic_id,epic_name,epic_status,epic_done,epic_total,{}",
+        STORY_HEADERS
+            .map(|header| format!("story_{}", header))
+            .join(",")
+    )
+    .unwrap();
+    for report in reports {
+        let epic = [
+            report.id.to_string(),
+            report.epic.name.clone(),
+            report.epic.status.to_string(),
+            report.progress.done.to_string(),
+            report.progress.total.to_string(),
+        ];
+        let rows = match report.story_reports.as_slice() {
+            [] => vec![Default::default()],
+            story_reports => story_reports.iter().map(story_cells).collect(),
+        };
+        for row in rows {
+            let fields = epic.iter().chain(&row).map(|text| field(text)).join(",");
+            writeln!(out, "{}", fields).unwrap();
+        }
+    }
+    out
+}
+
+// A single page with its styles inline, so the file can be mailed or opened as it is.
+fn html(reports: &[EpicReport], today: NaiveDate) -> String {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    };
+    let mut out = String::new();
+
+    out.push_str(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Board report</title>
+<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin: 0.5em 0 1.5em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #f0f0f0; }
+progress { width: 12em; }
+.status { font-weight: bold; }
+</style>
+</head>
+<body>
+<h1>Board report</h1>
+"#,
+    );
+    writeln!(out, "<p>Generated on {}.</p>", today).unwrap();
+
+    for report in reports {
+        writeln!(out, "<section>").unwrap();
+        writeln!(
+            out,
+            "<h2>{} {} <span class=\"status\">{}</span></h2>",
+            report.id,
+            escape(&report.epic.name),
+            escape(&report.epic.status.to_string())
+        )
+        .unwrap();
+        if !report.epic.description.is_empty() {
+            writeln!(out, "<p>{}</p>", escape(&report.epic.description)).unwrap();
+        }
+        writeln!(
+            out,
+            "<p><progress max=\"100\" value=\"{}\"></progress> {}</p>",
+            report.progress.percent(),
+            report.progress
+        )
+        .unwrap();
+
+        if !report.story_reports.is_empty() {
+            writeln!(out, "<table>").unwrap();
+            let headers = STORY_HEADERS.map(|header| format!("<th>{}</th>", header));
+            writeln!(out, "<tr>{}</tr>", headers.join("")).unwrap();
+            for story_report in &report.story_reports {
+                let cells =
+                    story_cells(story_report).map(|text| format!("<td>{}</td>", escape(&text)));
+                writeln!(out, "<tr>{}</tr>", cells.join("")).unwrap();
+            }
+            writeln!(out, "</table>").unwrap();
+        }
+        writeln!(out, "</section>").unwrap();
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::models::Workflow;
+
+    // Epic 1 with a closed story and an open one with awkward characters, and an empty epic 4.
+    fn board() -> DBState {
+        let mut epic = Epic::new("Accounts".to_owned(), "Sign up & login".to_owned());
+        epic.stories = vec![3, 2];
+        let mut done = Story::new("Sign up".to_owned(), "".to_owned());
+        done.status = Status::CLOSED;
+        done.points = Some(3);
+        let mut open = Story::new("Login, \"remember me\" | <b>".to_owned(), "".to_owned());
+        open.points = Some(2);
+
+        DBState {
+            last_item_id: 4,
+            epics: HashMap::from([
+                (1, epic),
+                (4, Epic::new("Billing".to_owned(), "".to_owned())),
+            ]),
+            stories: HashMap::from([(2, done), (3, open)]),
+            people: HashMap::new(),
+            labels: HashMap::new(),
+            workflow: Workflow::default(),
+            sprints: HashMap::new(),
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    #[test]
+    fn export_should_render_markdown_with_progress() {
+        let report = export(
+            &board(),
+            ExportFormat::Md,
+            &ExportFilter::default(),
+            today(),
+        )
+        .unwrap();
+
+        assert!(report.contains("## 1 Accounts (OPEN)"));
+        assert!(report.contains("Progress: 1/2 stories done (50%), 3/5 points"));
+        assert!(report.contains("| 2 | Sign up | CLOSED | MEDIUM | 3 | - | - |"));
+        assert!(report.contains("| 3 | Login, \"remember me\" \\| <b> | OPEN |"));
+        assert!(report.contains("## 4 Billing (OPEN)"));
+    }
+
+    #[test]
+    fn export_should_quote_csv_fields() {
+        let report = export(
+            &board(),
+            ExportFormat::Csv,
+            &ExportFilter::default(),
+            today(),
+        )
+        .unwrap();
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(
+            "epic_id,epic_name,epic_status,epic_done,epic_total,story_id,story_name,"
+        ));
+        assert_eq!(
+            lines[1],
+            "1,Accounts,OPEN,1,2,2,Sign up,CLOSED,MEDIUM,3,-,-"
+        );
+        assert_eq!(
+            lines[2],
+            "1,Accounts,OPEN,1,2,3,\"Login, \"\"remember me\"\" | <b>\",OPEN,MEDIUM,2,-,-"
+        );
+        assert_eq!(lines[3], "4,Billing,OPEN,0,0,,,,,,,");
+    }
+
+    #[test]
+    fn export_should_escape_html() {
+        let report = export(
+            &board(),
+            ExportFormat::Html,
+            &ExportFilter::default(),
+            today(),
+        )
+        .unwrap();
+
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<style>"));
+        assert!(report.contains("Sign up &amp; login"));
+        assert!(report.contains("&quot;remember me&quot; | &lt;b&gt;"));
+        assert!(report.contains("<progress max=\"100\" value=\"50\">"));
+        assert!(!report.contains("<b>"));
+    }
+
+    #[test]
+    fn export_should_apply_filters() {
+        let filter = ExportFilter {
+            epic_ids: vec![],
+            statuses: vec![Status::CLOSED],
+        };
+        let report = export(&board(), ExportFormat::Json, &filter, today()).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+        // the epics are still open, so only the one with a closed story is left
+        assert_eq!(report.as_array().unwrap().len(), 1);
+        assert_eq!(report[0]["id"], 1);
+        assert_eq!(report[0]["progress"]["total"], 2);
+        assert_eq!(report[0]["stories"].as_array().unwrap().len(), 1);
+        assert_eq!(report[0]["stories"][0]["id"], 2);
+
+        let filter = ExportFilter {
+            epic_ids: vec![4],
+            statuses: vec![],
+        };
+        let report = export(&board(), ExportFormat::Md, &filter, today()).unwrap();
+        assert!(!report.contains("Accounts"));
+        assert!(report.contains("Billing"));
+
+        let filter = ExportFilter {
+            epic_ids: vec![9],
+            statuses: vec![],
+        };
+        assert!(export(&board(), ExportFormat::Md, &filter, today()).is_err());
+    }
+}
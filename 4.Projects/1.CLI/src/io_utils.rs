@@ -1,4 +1,7 @@
-use std::io;
+use std::{
+    env, io,
+    process::{Command, Stdio},
+};
 
 pub fn get_user_input() -> String {
     let mut user_input = String::new();
@@ -8,6 +11,29 @@ pub fn get_user_input() -> String {
     user_input
 }
 
+// The width of the terminal in columns, 80 when it can't be told.
+pub fn terminal_width() -> usize {
+    if let Some(columns) = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+    {
+        return columns;
+    }
+
+    // `stty` reports the size of the terminal it gets as standard input
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .and_then(|output| {
+            let size = String::from_utf8(output.stdout).ok()?;
+            size.split_whitespace().nth(1)?.parse().ok()
+        })
+        .unwrap_or(80)
+}
+
 pub fn wait_for_key_press() {
     io::stdin().read_line(&mut String::new()).unwrap();
 }
@@ -1,4 +1,18 @@
-use std::rc::Rc;
+use std::{process::ExitCode, rc::Rc};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+
+mod cli;
+use cli::{Cli, Command};
+
+mod commands;
+use commands::*;
+
+mod config;
+use config::{Board, Config};
+
+mod export;
 
 mod models;
 
@@ -13,17 +27,160 @@ use io_utils::*;
 mod navigator;
 use navigator::*;
 
-fn main() {
-    let db = Rc::new(JiraDatabase::new("./data/db.json".to_owned()));
-    let mut navigator = Navigator::new(Rc::clone(&db));
+// Errors end the process with a failure exit code so scripts can tell them apart.
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {:#}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let config = Config::load()?;
+    let board = config.resolve_board(cli.db_path, cli.board, cli.backend)?;
+
+    match cli.command {
+        Some(Command::Init) => init(&config, &board),
+        Some(Command::Migrate) => migrate(&board),
+        Some(Command::Fsck { repair }) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            fsck(&db, repair)
+        }
+        Some(Command::Epic(command)) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_epic_command(&db, command)
+        }
+        Some(Command::Story(command)) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_story_command(&db, command)
+        }
+        Some(Command::Person(command)) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_person_command(&db, command)
+        }
+        Some(Command::Label(command)) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_label_command(&db, command)
+        }
+        Some(Command::Export {
+            format,
+            epic_ids,
+            statuses,
+            output,
+        }) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_export_command(&db, format, epic_ids, statuses, output)
+        }
+        Some(Command::Sprint(command)) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_sprint_command(&db, command)
+        }
+        Some(Command::Log {
+            epic_id,
+            story_id,
+            limit,
+            json,
+        }) => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            run_log_command(&db, epic_id, story_id, limit, json)
+        }
+        None => {
+            let db = open_board(&config, &board, cli.restore_backup)?;
+            let navigator = Navigator::new(Rc::new(db))
+                .with_boards(config.boards()?, board.name)
+                .with_user(config.current_user());
+            run_navigator(navigator)
+        }
+    }
+}
+
+// Changes made through the board are recorded in its history as the current user. A workflow
+// defined in the config is stored with the board, so it also applies when opened by path.
+fn open_board(config: &Config, board: &Board, restore_backup: bool) -> Result<JiraDatabase> {
+    let db = JiraDatabase::new(board.backend, board.path.clone(), restore_backup)?
+        .with_actor(config.current_user());
 
+    if let Some(workflow) = &board.workflow {
+        db.set_workflow(workflow.clone()).with_context(|| {
+            anyhow!("failed to apply the workflow of {}!", board.display_name())
+        })?;
+    }
+    Ok(db)
+}
+
+// The workflow defined in the config is stored with the new board right away.
+fn init(config: &Config, board: &Board) -> Result<()> {
+    JiraDatabase::init(board.backend, &board.path)
+        .with_context(|| anyhow!("failed to create {}!", board.display_name()))?;
+    open_board(config, board, false)?;
+
+    println!("created {}", board.display_name());
+    Ok(())
+}
+
+fn migrate(board: &Board) -> Result<()> {
+    if board.backend != Backend::Json {
+        return Err(anyhow!("only json boards need to be migrated"));
+    }
+
+    let (from_version, to_version) = migrate_json_board(board.path.clone())?;
+
+    if from_version == to_version {
+        println!(
+            "{} is already at schema version {}",
+            board.display_name(),
+            to_version
+        );
+    } else {
+        println!(
+            "migrated {} from schema version {} to {}",
+            board.display_name(),
+            from_version,
+            to_version
+        );
+    }
+
+    Ok(())
+}
+
+fn fsck(db: &JiraDatabase, repair: bool) -> Result<()> {
+    let problems = if repair {
+        db.repair_integrity()?
+    } else {
+        db.check_integrity()?
+    };
+
+    if problems.is_empty() {
+        println!("no problems found");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    if repair {
+        println!("repaired {} problem(s)", problems.len());
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "found {} problem(s), run `my-jira fsck --repair` to fix them",
+            problems.len()
+        ))
+    }
+}
+
+fn run_navigator(mut navigator: Navigator) -> Result<()> {
     loop {
         clearscreen::clear().unwrap();
 
         if let Some(page) = navigator.get_current_page() {
             if let Err(error) = page.draw_page() {
                 println!(
-                    "Error rendering page: {}\nPress any key to continue...",
+                    "Error rendering page: {:#}\nPress any key to continue...",
                     error
                 );
                 wait_for_key_press();
@@ -31,10 +188,10 @@ fn main() {
 
             let user_input = get_user_input();
 
-            match page.handle_input(user_input.trim()) {
+            match navigator.handle_input(user_input.trim()) {
                 Err(error) => {
                     println!(
-                        "Error getting user input: {}\nPress any key to continue...",
+                        "Error getting user input: {:#}\nPress any key to continue...",
                         error
                     );
                     wait_for_key_press();
@@ -42,7 +199,7 @@ fn main() {
                 Ok(action) => {
                     if let Some(action) = action {
                         if let Err(error) = navigator.handle_action(action) {
-                            println!("Error handling processing user input: {}\nPress any key to continue...", error);
+                            println!("Error handling processing user input: {:#}\nPress any key to continue...", error);
                             wait_for_key_press();
                         }
                     }
@@ -52,4 +209,6 @@ fn main() {
             break;
         }
     }
+
+    Ok(())
 }
@@ -1,5 +1,11 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -8,29 +14,365 @@ pub enum Action {
     NavigateToStoryDetail { epic_id: u32, story_id: u32 },
     NavigateToPreviousPage,
     CreateEpic,
+    UpdateEpic { epic_id: u32 },
     UpdateEpicStatus { epic_id: u32 },
     DeleteEpic { epic_id: u32 },
     CreateStory { epic_id: u32 },
-    UpdateStoryStatus { story_id: u32 },
+    UpdateStory { story_id: u32 },
+    // without a status to move to, the user picks one of the next states of the workflow
+    UpdateStoryStatus { story_id: u32, to: Option<Status> },
+    UpdateStoryEstimate { story_id: u32 },
     DeleteStory { epic_id: u32, story_id: u32 },
+    MoveStory { epic_id: u32, story_id: u32 },
+    AssignEpic { epic_id: u32 },
+    UnassignEpic { epic_id: u32 },
+    AssignStory { story_id: u32 },
+    UnassignStory { story_id: u32 },
+    NavigateToMyWork,
+    NavigateToUpcoming { days: u32 },
+    NavigateToSprint,
+    // the stories of an epic, or of every epic, in a column per state of the workflow
+    NavigateToKanban { epic_id: Option<u32> },
+    // without a query the user is asked for one
+    NavigateToSearch { query: Option<String> },
+    AddSprintStory { sprint_id: u32 },
+    RemoveSprintStory { sprint_id: u32 },
+    UpdateEpicDue { epic_id: u32 },
+    UpdateStoryDue { story_id: u32 },
+    AddChecklistItem { story_id: u32 },
+    ToggleChecklistItem { story_id: u32 },
+    RemoveChecklistItem { story_id: u32 },
+    MoveChecklistItem { story_id: u32 },
+    AddDependency { story_id: u32 },
+    RemoveDependency { story_id: u32 },
+    AddEpicLabel { epic_id: u32 },
+    RemoveEpicLabel { epic_id: u32 },
+    AddStoryLabel { story_id: u32 },
+    RemoveStoryLabel { story_id: u32 },
+    AddComment { story_id: u32 },
+    DeleteComment { story_id: u32 },
+    NavigateToComments { story_id: u32 },
+    NavigateToHistory { item: ItemKind, item_id: u32 },
+    FilterEpics { label: Option<String> },
+    FilterStories { epic_id: u32, label: Option<String> },
+    ViewEpics { view: ListView },
+    ViewStories { epic_id: u32, view: ListView },
+    NavigateToBoardSwitcher,
+    SwitchBoard { name: String },
+    Undo,
+    Redo,
     Exit,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub enum Status {
-    Open,
-    InProgress,
-    Resolved,
-    Closed,
+impl Action {
+    // Actions that can write to the board, and so can be undone.
+    pub fn changes_board(&self) -> bool {
+        matches!(
+            self,
+            Action::CreateEpic
+                | Action::UpdateEpic { .. }
+                | Action::UpdateEpicStatus { .. }
+                | Action::DeleteEpic { .. }
+                | Action::CreateStory { .. }
+                | Action::UpdateStory { .. }
+                | Action::UpdateStoryStatus { .. }
+                | Action::UpdateStoryEstimate { .. }
+                | Action::DeleteStory { .. }
+                | Action::MoveStory { .. }
+                | Action::AssignEpic { .. }
+                | Action::UnassignEpic { .. }
+                | Action::AssignStory { .. }
+                | Action::UnassignStory { .. }
+                | Action::AddEpicLabel { .. }
+                | Action::RemoveEpicLabel { .. }
+                | Action::AddStoryLabel { .. }
+                | Action::RemoveStoryLabel { .. }
+                | Action::UpdateEpicDue { .. }
+                | Action::UpdateStoryDue { .. }
+                | Action::AddChecklistItem { .. }
+                | Action::ToggleChecklistItem { .. }
+                | Action::RemoveChecklistItem { .. }
+                | Action::MoveChecklistItem { .. }
+                | Action::AddDependency { .. }
+                | Action::RemoveDependency { .. }
+                | Action::AddSprintStory { .. }
+                | Action::RemoveSprintStory { .. }
+                | Action::AddComment { .. }
+                | Action::DeleteComment { .. }
+        )
+    }
+}
+
+// How a list page shows its rows. The navigator remembers it while moving between pages.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ListView {
+    pub label: Option<String>,
+    // `None` keeps the list's usual order
+    pub sort: Option<SortKey>,
+    // statuses whose items are left out
+    pub hidden: Vec<Status>,
+    // counted from 0, pages past the end show the last one
+    pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortKey {
+    Id,
+    Name,
+    // in the order of the workflow
+    Status,
+}
+
+impl SortKey {
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "id" => Ok(SortKey::Id),
+            "name" => Ok(SortKey::Name),
+            "status" => Ok(SortKey::Status),
+            other => Err(anyhow!(
+                "can't sort by {:?}, use id, name or status!",
+                other
+            )),
+        }
+    }
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Id => write!(f, "id"),
+            SortKey::Name => write!(f, "name"),
+            SortKey::Status => write!(f, "status"),
+        }
+    }
+}
+
+// The name of a state of the board's workflow. Boards that don't define a workflow of their own
+// use the default one, made of the four states below.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[serde(transparent)]
+pub struct Status(Cow<'static, str>);
+
+impl Status {
+    pub const OPEN: Status = Status(Cow::Borrowed("Open"));
+    pub const IN_PROGRESS: Status = Status(Cow::Borrowed("InProgress"));
+    pub const RESOLVED: Status = Status(Cow::Borrowed("Resolved"));
+    pub const CLOSED: Status = Status(Cow::Borrowed("Closed"));
+
+    pub fn new(name: &str) -> Self {
+        Status(Cow::Owned(name.to_owned()))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    // Names are compared ignoring case and separators, so `in-progress` finds `InProgress`.
+    fn key(name: &str) -> String {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
 }
 
 impl Display for Status {
+    // `InProgress` is shown as `IN PROGRESS`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut previous_lowercase = false;
+        for c in self.0.chars() {
+            if c.is_uppercase() && previous_lowercase {
+                write!(f, " ")?;
+            }
+            previous_lowercase = c.is_lowercase();
+            write!(f, "{}", c.to_uppercase())?;
+        }
+        Ok(())
+    }
+}
+
+// The states epics and stories of a board go through. Items start in the first state, and work
+// in a terminal state counts as done.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Workflow {
+    pub states: Vec<Status>,
+    pub terminal: Vec<Status>,
+    // the states each state can move to, a state without an entry can't be left
+    pub transitions: BTreeMap<Status, Vec<Status>>,
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Workflow::new(
+            &[
+                Status::OPEN.name(),
+                Status::IN_PROGRESS.name(),
+                Status::RESOLVED.name(),
+                Status::CLOSED.name(),
+            ],
+            &[Status::RESOLVED.name(), Status::CLOSED.name()],
+            None,
+        )
+        .expect("the default workflow should be valid")
+    }
+}
+
+impl Workflow {
+    // Terminal states and transitions may refer to states by any spelling `find_state` accepts.
+    // Without transitions, any state can move to any other one.
+    pub fn new(
+        states: &[&str],
+        terminal: &[&str],
+        transitions: Option<&BTreeMap<String, Vec<String>>>,
+    ) -> Result<Self> {
+        let mut workflow = Workflow {
+            states: vec![],
+            terminal: vec![],
+            transitions: BTreeMap::new(),
+        };
+
+        for name in states {
+            if Status::key(name).is_empty() {
+                return Err(anyhow!("workflow state names can't be empty!"));
+            }
+            if workflow.find_state(name).is_ok() {
+                return Err(anyhow!("workflow state {:?} is defined twice!", name));
+            }
+            workflow.states.push(Status::new(name));
+        }
+        if workflow.states.is_empty() {
+            return Err(anyhow!("a workflow needs at least one state!"));
+        }
+
+        let mut terminal_states = vec![];
+        for name in terminal {
+            terminal_states.push(workflow.find_state(name)?);
+        }
+        workflow.terminal = workflow
+            .states
+            .iter()
+            .filter(|state| terminal_states.contains(state))
+            .cloned()
+            .collect();
+
+        for from in &workflow.states {
+            let next_states = match transitions {
+                None => workflow
+                    .states
+                    .iter()
+                    .filter(|state| *state != from)
+                    .cloned()
+                    .collect(),
+                Some(transitions) => {
+                    let mut next_states = vec![];
+                    for (name, names) in transitions {
+                        if workflow.find_state(name)? == *from {
+                            for name in names {
+                                next_states.push(workflow.find_state(name)?);
+                            }
+                        }
+                    }
+                    // offered in the order the states are defined in
+                    workflow
+                        .states
+                        .iter()
+                        .filter(|state| next_states.contains(state))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                }
+            };
+
+            if !next_states.is_empty() {
+                workflow.transitions.insert(from.clone(), next_states);
+            }
+        }
+
+        Ok(workflow)
+    }
+
+    pub fn initial_state(&self) -> &Status {
+        &self.states[0]
+    }
+
+    pub fn contains(&self, status: &Status) -> bool {
+        self.states.contains(status)
+    }
+
+    // Work in a terminal state no longer counts as remaining.
+    pub fn is_done(&self, status: &Status) -> bool {
+        self.terminal.contains(status)
+    }
+
+    // Work that has left the initial state without being done yet.
+    pub fn is_in_progress(&self, status: &Status) -> bool {
+        status != self.initial_state() && !self.is_done(status)
+    }
+
+    pub fn next_states(&self, from: &Status) -> &[Status] {
+        self.transitions.get(from).map_or(&[], Vec::as_slice)
+    }
+
+    // Staying in the same state is always allowed.
+    pub fn check_transition(&self, from: &Status, to: &Status) -> Result<()> {
+        if !self.contains(to) {
+            return Err(anyhow!("{} is not a state of the board's workflow!", to));
+        }
+        if from != to && !self.next_states(from).contains(to) {
+            return Err(anyhow!(
+                "the workflow doesn't allow moving from {} to {}!",
+                from,
+                to
+            ));
+        }
+        Ok(())
+    }
+
+    // Where a status comes in the workflow, statuses it doesn't know after all of its states.
+    pub fn state_position(&self, status: &Status) -> usize {
+        self.states
+            .iter()
+            .position(|state| state == status)
+            .unwrap_or(self.states.len())
+    }
+
+    pub fn find_state(&self, name: &str) -> Result<Status> {
+        let key = Status::key(name);
+        self.states
+            .iter()
+            .find(|state| Status::key(state.name()) == key)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown status {:?}, expected one of: {}",
+                    name,
+                    self.states
+                        .iter()
+                        .map(Status::name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+// Declared from most to least urgent, so sorting by priority puts urgent work first.
+#[derive(
+    clap::ValueEnum, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy,
+)]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Status::Open => write!(f, "OPEN"),
-            Status::InProgress => write!(f, "IN PROGRESS"),
-            Status::Resolved => write!(f, "RESOLVED"),
-            Status::Closed => write!(f, "CLOSED"),
+            Priority::Critical => write!(f, "CRITICAL"),
+            Priority::High => write!(f, "HIGH"),
+            Priority::Medium => write!(f, "MEDIUM"),
+            Priority::Low => write!(f, "LOW"),
         }
     }
 }
@@ -41,6 +383,9 @@ pub struct Epic {
     pub description: String,
     pub status: Status,
     pub stories: Vec<u32>,
+    pub assignee: Option<String>,
+    pub labels: Vec<u32>,
+    pub due: Option<NaiveDate>,
 }
 
 impl Epic {
@@ -49,8 +394,11 @@ impl Epic {
         Epic {
             name,
             description,
-            status: Status::Open,
+            status: Status::OPEN,
             stories: vec![],
+            assignee: None,
+            labels: vec![],
+            due: None,
         }
     }
 }
@@ -60,20 +408,409 @@ pub struct Story {
     pub name: String,
     pub description: String,
     pub status: Status,
+    pub assignee: Option<String>,
+    pub priority: Priority,
+    pub points: Option<u32>,
+    pub labels: Vec<u32>,
+    // oldest first
+    pub comments: Vec<Comment>,
+    pub due: Option<NaiveDate>,
+    // steps too small to be stories of their own, in the order they should be done
+    pub checklist: Vec<ChecklistItem>,
+    // ids of the stories that have to be done before this one, from any epic
+    pub blocked_by: Vec<u32>,
 }
 
 impl Story {
     pub fn new(name: String, description: String) -> Self {
-        // by default the status should be set to open
+        // by default the status should be set to open, with a medium priority and no estimate
         Story {
             name,
             description,
-            status: Status::Open,
+            status: Status::OPEN,
+            assignee: None,
+            priority: Priority::Medium,
+            points: None,
+            labels: vec![],
+            comments: vec![],
+            due: None,
+            checklist: vec![],
+            blocked_by: vec![],
+        }
+    }
+
+    // How many checklist items are done, out of how many.
+    pub fn checklist_progress(&self) -> (usize, usize) {
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        (done, self.checklist.len())
+    }
+}
+
+// A dependency between a story and another one, seen from the first story.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Dependency {
+    BlockedBy(u32),
+    Blocks(u32),
+}
+
+impl Display for Dependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dependency::BlockedBy(story_id) => write!(f, "blocked by story {}", story_id),
+            Dependency::Blocks(story_id) => write!(f, "blocks story {}", story_id),
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    pub fn new(text: String) -> Self {
+        ChecklistItem { text, done: false }
+    }
+}
+
+impl Display for ChecklistItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mark = if self.done { "x" } else { " " };
+        write!(f, "[{}] {}", mark, self.text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Comment {
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Comment {
+    pub fn new(author: String, body: String) -> Self {
+        Comment {
+            author,
+            body,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// Timestamps are stored in UTC but shown in the local time zone.
+pub fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+// Accepts a date like `2026-11-01` or a number of days from `today` like `+3d`.
+pub fn parse_due_date(input: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let input = input.trim();
+
+    if let Some(days) = input
+        .strip_prefix('+')
+        .and_then(|days| days.strip_suffix('d'))
+    {
+        let days = days
+            .parse::<u64>()
+            .map_err(|_| anyhow!("invalid number of days in {:?}", input))?;
+        return today
+            .checked_add_days(Days::new(days))
+            .ok_or_else(|| anyhow!("{:?} is too far in the future", input));
+    }
+
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid due date {:?}, use YYYY-MM-DD or +Nd", input))
+}
+
+// Work that is done can't be late anymore.
+pub fn is_overdue(due: Option<NaiveDate>, done: bool, today: NaiveDate) -> bool {
+    due.is_some_and(|due| due < today) && !done
+}
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            format_timestamp(&self.created_at),
+            self.author,
+            self.body
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Epic,
+    Story,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemKind::Epic => write!(f, "epic"),
+            ItemKind::Story => write!(f, "story"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    Created { name: String },
+    Deleted { name: String },
+    StatusChanged { from: Status, to: Status },
+    // names of the fields that changed, like `name` or `assignee`
+    Edited { fields: Vec<String> },
+    Moved { from_epic_id: u32, to_epic_id: u32 },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Created { name } => write!(f, "created {:?}", name),
+            Change::Deleted { name } => write!(f, "deleted {:?}", name),
+            Change::StatusChanged { from, to } => write!(f, "status {} -> {}", from, to),
+            Change::Edited { fields } => write!(f, "edited {}", fields.join(", ")),
+            Change::Moved {
+                from_epic_id,
+                to_epic_id,
+            } => write!(f, "moved from epic {} to epic {}", from_epic_id, to_epic_id),
+        }
+    }
+}
+
+// One entry of a board's append-only history.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Event {
+    pub timestamp: DateTime<Utc>,
+    // the user the change was made as, if one was configured
+    pub actor: Option<String>,
+    pub item: ItemKind,
+    pub item_id: u32,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}: {}",
+            format_timestamp(&self.timestamp),
+            self.actor.as_deref().unwrap_or("-"),
+            self.item,
+            self.item_id,
+            self.change
+        )
+    }
+}
+
+// Story points of a set of stories, split by whether the work is done.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct PointsSummary {
+    pub total: u32,
+    pub completed: u32,
+}
+
+impl PointsSummary {
+    pub fn from_stories<'a>(
+        stories: impl IntoIterator<Item = &'a Story>,
+        workflow: &Workflow,
+    ) -> Self {
+        // estimates aren't capped, so huge ones saturate instead of overflowing
+        let mut summary = Self::default();
+        for story in stories {
+            let points = story.points.unwrap_or(0);
+            summary.total = summary.total.saturating_add(points);
+            if workflow.is_done(&story.status) {
+                summary.completed = summary.completed.saturating_add(points);
+            }
+        }
+        summary
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.total - self.completed
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Person {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Label {
+    pub name: String,
+}
+
+impl Label {
+    // Label names are compared case-insensitively, so `Backend` and `backend` are one label.
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name.trim())
+    }
+}
+
+// A timeboxed set of stories. The sprint that isn't closed yet is the active one, a board has
+// at most one of them.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Sprint {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    // ids of the stories planned for the sprint, from any epic
+    pub stories: Vec<u32>,
+    pub closed: bool,
+}
+
+// Work left in a sprint at the end of each of its days so far.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Burndown {
+    // `points`, or `stories` when none of the sprint's stories is estimated
+    pub unit: &'static str,
+    pub total: u32,
+    pub days: Vec<BurndownDay>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BurndownDay {
+    pub date: NaiveDate,
+    pub remaining: u32,
+    // what would be left if the work went down evenly to nothing by the last day
+    pub ideal: u32,
+}
+
+impl Sprint {
+    pub fn new(name: String, start: NaiveDate, end: NaiveDate) -> Self {
+        Sprint {
+            name,
+            start,
+            end,
+            stories: vec![],
+            closed: false,
+        }
+    }
+
+    // Replays the status changes recorded in the history to tell which stories were done at the
+    // end of each day, from the start of the sprint up to its end or today.
+    pub fn burndown(
+        &self,
+        stories: &HashMap<u32, Story>,
+        events: &[Event],
+        workflow: &Workflow,
+        today: NaiveDate,
+    ) -> Burndown {
+        let stories = self
+            .stories
+            .iter()
+            .filter_map(|id| Some((*id, stories.get(id)?)))
+            .collect::<Vec<_>>();
+        let estimated = stories.iter().any(|(_, story)| story.points.is_some());
+        let weight = |story: &Story| {
+            if estimated {
+                story.points.unwrap_or(0)
+            } else {
+                1
+            }
+        };
+        let total = stories.iter().map(|(_, story)| weight(story)).sum();
+        let length = (self.end - self.start).num_days().max(1);
+
+        let days = self
+            .start
+            .iter_days()
+            .take_while(|day| *day <= self.end.min(today))
+            .map(|date| {
+                let remaining = stories
+                    .iter()
+                    .filter(|(id, story)| !workflow.is_done(status_on(*id, story, events, date)))
+                    .map(|(_, story)| weight(story))
+                    .sum();
+                let days_left = (self.end - date).num_days();
+                let ideal = (i64::from(total) * days_left / length) as u32;
+
+                BurndownDay {
+                    date,
+                    remaining,
+                    ideal,
+                }
+            })
+            .collect();
+
+        Burndown {
+            unit: if estimated { "points" } else { "stories" },
+            total,
+            days,
+        }
+    }
+}
+
+impl Display for Burndown {
+    // One bar per day, `#` for the work remaining, topped up with `.` to the ideal pace.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const WIDTH: u32 = 30;
+        let bar_length = |value: u32| (value * WIDTH).checked_div(self.total).unwrap_or(0) as usize;
+
+        writeln!(
+            f,
+            "burndown ({} remaining, `.` marks the ideal pace)",
+            self.unit
+        )?;
+        for day in &self.days {
+            let remaining = bar_length(day.remaining);
+            let ideal = bar_length(day.ideal);
+            let bar = format!(
+                "{}{}",
+                "#".repeat(remaining),
+                ".".repeat(ideal.saturating_sub(remaining))
+            );
+            writeln!(
+                f,
+                "{} | {:<width$} {}",
+                day.date,
+                bar,
+                day.remaining,
+                width = WIDTH as usize
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The status a story was in at the end of `day`. Before its first recorded change, a story was
+// in the status that change started from.
+fn status_on<'a>(
+    story_id: u32,
+    story: &'a Story,
+    events: &'a [Event],
+    day: NaiveDate,
+) -> &'a Status {
+    let mut status = None;
+    let mut next_change_from = None;
+
+    for event in events
+        .iter()
+        .filter(|event| event.item == ItemKind::Story && event.item_id == story_id)
+    {
+        if let Change::StatusChanged { from, to } = &event.change {
+            if event.timestamp.with_timezone(&Local).date_naive() <= day {
+                status = Some(to);
+            } else if next_change_from.is_none() {
+                next_change_from = Some(from);
+            }
+        }
+    }
+
+    status.or(next_change_from).unwrap_or(&story.status)
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
 pub struct DBState {
     // This struct represents the entire db state which includes the last_item_id, epics, and stories
     pub last_item_id: u32,
@@ -81,4 +818,246 @@ pub struct DBState {
     pub epics: HashMap<u32, Epic>,
 
     pub stories: HashMap<u32, Story>,
+
+    // team members keyed by the username epics and stories are assigned to
+    pub people: HashMap<String, Person>,
+
+    // labels have their own ids, so renaming one doesn't touch the items carrying it
+    pub labels: HashMap<u32, Label>,
+
+    // the states epics and stories go through on this board
+    pub workflow: Workflow,
+
+    // sprints have their own ids, handed out in the order the sprints were started
+    pub sprints: HashMap<u32, Sprint>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone};
+
+    #[test]
+    fn burndown_should_replay_status_changes_day_by_day() {
+        let resolved_on = |story_id, day| Event {
+            timestamp: Local
+                .with_ymd_and_hms(2026, 11, day, 12, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+            actor: None,
+            item: ItemKind::Story,
+            item_id: story_id,
+            change: Change::StatusChanged {
+                from: Status::OPEN,
+                to: Status::RESOLVED,
+            },
+        };
+        let mut stories = HashMap::new();
+        for (story_id, points) in [(1, 3), (2, 2)] {
+            let mut story = Story::new("".to_owned(), "".to_owned());
+            story.points = Some(points);
+            story.status = Status::RESOLVED;
+            stories.insert(story_id, story);
+        }
+        let mut sprint = Sprint::new(
+            "".to_owned(),
+            NaiveDate::from_ymd_opt(2026, 11, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 5).unwrap(),
+        );
+        sprint.stories = vec![1, 2];
+        let events = [resolved_on(1, 3), resolved_on(2, 5)];
+
+        // story 2 is only resolved after today, so it still counts as remaining
+        let burndown = sprint.burndown(
+            &stories,
+            &events,
+            &Workflow::default(),
+            NaiveDate::from_ymd_opt(2026, 11, 4).unwrap(),
+        );
+
+        assert_eq!(burndown.unit, "points");
+        assert_eq!(burndown.total, 5);
+        assert_eq!(
+            burndown
+                .days
+                .iter()
+                .map(|day| (day.date.day(), day.remaining, day.ideal))
+                .collect::<Vec<_>>(),
+            vec![(2, 5, 5), (3, 2, 3), (4, 2, 1)]
+        );
+        assert!(burndown
+            .to_string()
+            .contains("2026-11-03 | ############..."));
+    }
+
+    #[test]
+    fn points_summary_should_split_done_and_remaining_points() {
+        let mut open = Story::new("".to_owned(), "".to_owned());
+        open.points = Some(5);
+        let mut resolved = Story::new("".to_owned(), "".to_owned());
+        resolved.points = Some(3);
+        resolved.status = Status::RESOLVED;
+        let mut closed = Story::new("".to_owned(), "".to_owned());
+        closed.points = Some(2);
+        closed.status = Status::CLOSED;
+        let unestimated = Story::new("".to_owned(), "".to_owned());
+
+        let summary = PointsSummary::from_stories(
+            &[open, resolved, closed, unestimated],
+            &Workflow::default(),
+        );
+
+        assert_eq!(summary.total, 10);
+        assert_eq!(summary.completed, 5);
+        assert_eq!(summary.remaining(), 5);
+    }
+
+    #[test]
+    fn points_summary_should_saturate_huge_estimates() {
+        let mut open = Story::new("".to_owned(), "".to_owned());
+        open.points = Some(u32::MAX);
+        let mut closed = open.clone();
+        closed.status = Status::CLOSED;
+
+        let summary = PointsSummary::from_stories(&[open, closed], &Workflow::default());
+
+        assert_eq!(summary.total, u32::MAX);
+        assert_eq!(summary.completed, u32::MAX);
+        assert_eq!(summary.remaining(), 0);
+    }
+
+    #[test]
+    fn parse_due_date_should_accept_dates_and_relative_days() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 30).unwrap();
+
+        assert_eq!(
+            parse_due_date("2026-11-01", today).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+        assert_eq!(
+            parse_due_date(" +3d ", today).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 2).unwrap()
+        );
+        assert_eq!(parse_due_date("+0d", today).unwrap(), today);
+        assert!(parse_due_date("+3", today).is_err());
+        assert!(parse_due_date("+xd", today).is_err());
+        assert!(parse_due_date("2026-02-30", today).is_err());
+        assert!(parse_due_date("tomorrow", today).is_err());
+    }
+
+    #[test]
+    fn is_overdue_should_ignore_done_work() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 30).unwrap();
+        let yesterday = today.pred_opt();
+
+        assert!(is_overdue(yesterday, false, today));
+        assert!(!is_overdue(yesterday, true, today));
+        assert!(!is_overdue(Some(today), false, today));
+        assert!(!is_overdue(None, false, today));
+    }
+
+    fn review_workflow() -> Workflow {
+        let transitions = BTreeMap::from([
+            ("todo".to_owned(), vec!["doing".to_owned()]),
+            (
+                "Doing".to_owned(),
+                vec!["In Review".to_owned(), "Todo".to_owned()],
+            ),
+            ("in-review".to_owned(), vec!["Done".to_owned()]),
+        ]);
+        Workflow::new(
+            &["Todo", "Doing", "InReview", "Done"],
+            &["done"],
+            Some(&transitions),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn workflow_should_only_allow_defined_transitions() {
+        let workflow = review_workflow();
+        let [todo, doing, review, done] = ["Todo", "Doing", "InReview", "Done"].map(Status::new);
+
+        assert_eq!(workflow.initial_state(), &todo);
+        assert_eq!(workflow.next_states(&doing), [todo.clone(), review.clone()]);
+        assert!(workflow.next_states(&done).is_empty());
+        assert!(workflow.check_transition(&todo, &doing).is_ok());
+        assert!(workflow.check_transition(&done, &done).is_ok());
+        assert!(workflow.check_transition(&todo, &done).is_err());
+        assert!(workflow.check_transition(&todo, &Status::CLOSED).is_err());
+
+        assert!(workflow.is_done(&done));
+        assert!(workflow.is_in_progress(&review));
+        assert!(!workflow.is_in_progress(&todo));
+        assert_eq!(workflow.find_state("in review").unwrap(), review);
+        assert!(workflow.find_state("closed").is_err());
+    }
+
+    #[test]
+    fn workflow_should_reject_invalid_definitions() {
+        assert!(Workflow::new(&[], &[], None).is_err());
+        assert!(Workflow::new(&["Todo", "to-do"], &[], None).is_err());
+        assert!(Workflow::new(&["Todo", " "], &[], None).is_err());
+        assert!(Workflow::new(&["Todo"], &["Done"], None).is_err());
+
+        let transitions = BTreeMap::from([("Todo".to_owned(), vec!["Done".to_owned()])]);
+        assert!(Workflow::new(&["Todo"], &[], Some(&transitions)).is_err());
+    }
+
+    #[test]
+    fn default_workflow_should_allow_any_transition() {
+        let workflow = Workflow::default();
+
+        assert_eq!(workflow.initial_state(), &Status::OPEN);
+        assert!(workflow
+            .check_transition(&Status::CLOSED, &Status::OPEN)
+            .is_ok());
+        assert_eq!(workflow.next_states(&Status::OPEN).len(), 3);
+        assert!(workflow.is_done(&Status::RESOLVED));
+        assert_eq!(Status::IN_PROGRESS.to_string(), "IN PROGRESS");
+        assert_eq!(
+            workflow.find_state("in-progress").unwrap(),
+            Status::IN_PROGRESS
+        );
+    }
+
+    #[test]
+    fn events_should_round_trip_through_json() {
+        let event = Event {
+            timestamp: Utc::now(),
+            actor: Some("alice".to_owned()),
+            item: ItemKind::Story,
+            item_id: 2,
+            change: Change::StatusChanged {
+                from: Status::OPEN,
+                to: Status::IN_PROGRESS,
+            },
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["change"], "status_changed");
+        assert_eq!(json["item"], "story");
+        assert_eq!(serde_json::from_value::<Event>(json).unwrap(), event);
+    }
+
+    #[test]
+    fn priorities_should_sort_most_urgent_first() {
+        let mut priorities = vec![
+            Priority::Low,
+            Priority::Critical,
+            Priority::Medium,
+            Priority::High,
+        ];
+        priorities.sort();
+
+        assert_eq!(
+            priorities,
+            vec![
+                Priority::Critical,
+                Priority::High,
+                Priority::Medium,
+                Priority::Low
+            ]
+        );
+    }
 }
@@ -1,37 +1,112 @@
 use anyhow::{anyhow, Context, Ok, Result};
-use std::rc::Rc;
+use itertools::Itertools;
+use std::{collections::HashMap, rc::Rc};
 
 use crate::{
-    db::JiraDatabase,
-    models::Action,
-    ui::{EpicDetail, HomePage, Page, Prompts, StoryDetail},
+    config::Board,
+    db::{revision::Revision, JiraDatabase},
+    models::{Action, ChecklistItem, Comment, Dependency, ListView, Person, Status},
+    ui::{
+        BoardSwitcher, Comments, EpicDetail, History, HomePage, Kanban, MyWork, Page, Prompts,
+        Search, SprintPage, StoryDetail, Upcoming,
+    },
 };
 
 pub struct Navigator {
     pages: Vec<Box<dyn Page>>,
     prompts: Prompts,
     db: Rc<JiraDatabase>,
+    boards: Vec<Board>,
+    current_board: Option<String>,
+    user: Option<String>,
+    // changes made during this session, most recent last
+    undo: Vec<Revision>,
+    redo: Vec<Revision>,
+    // how the epics and the stories of each epic were last shown
+    epics_view: ListView,
+    story_views: HashMap<u32, ListView>,
 }
 
 impl Navigator {
     pub fn new(db: Rc<JiraDatabase>) -> Self {
         Self {
-            pages: vec![Box::new(HomePage { db: Rc::clone(&db) })],
+            pages: vec![Box::new(HomePage {
+                db: Rc::clone(&db),
+                view: ListView::default(),
+            })],
             prompts: Prompts::new(),
             db,
+            boards: vec![],
+            current_board: None,
+            user: None,
+            undo: vec![],
+            redo: vec![],
+            epics_view: ListView::default(),
+            story_views: HashMap::new(),
         }
     }
 
-    pub fn get_current_page(&self) -> Option<&Box<dyn Page>> {
-        self.pages.last()
+    pub fn with_boards(mut self, boards: Vec<Board>, current_board: Option<String>) -> Self {
+        self.boards = boards;
+        self.current_board = current_board;
+        self
     }
 
+    pub fn with_user(mut self, user: Option<String>) -> Self {
+        self.user = user;
+        self
+    }
+
+    pub fn get_current_page(&self) -> Option<&dyn Page> {
+        self.pages.last().map(|page| page.as_ref())
+    }
+
+    // `z` and `Z` undo and redo from any page, other input is left to the current page. A `/`
+    // the page has no use for, like `/label` outside of the filtered lists, starts a search.
+    pub fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "z" => Ok(Some(Action::Undo)),
+            "Z" => Ok(Some(Action::Redo)),
+            input => {
+                let action = match self.get_current_page() {
+                    Some(page) => page.handle_input(input)?,
+                    None => return Ok(None),
+                };
+                Ok(action.or_else(|| {
+                    let query = input.strip_prefix('/')?.trim();
+                    Some(Action::NavigateToSearch {
+                        query: (!query.is_empty()).then(|| query.to_owned()),
+                    })
+                }))
+            }
+        }
+    }
+
+    // Whatever an action changed on the board is remembered so it can be undone.
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
+        if !action.changes_board() {
+            return self.perform_action(action);
+        }
+
+        // a cancelled or failed action may still have changed something before giving up
+        let db = Rc::clone(&self.db);
+        let (result, revision) = db.record_revision(|| self.perform_action(action));
+
+        if let Some(revision) = revision {
+            self.undo.push(revision);
+            self.redo.clear();
+        }
+
+        result
+    }
+
+    fn perform_action(&mut self, action: Action) -> Result<()> {
         match action {
             Action::NavigateToEpicDetail { epic_id } => {
                 self.pages.push(Box::new(EpicDetail {
                     epic_id,
                     db: Rc::clone(&self.db),
+                    view: self.story_views.get(&epic_id).cloned().unwrap_or_default(),
                 }));
             }
             Action::NavigateToStoryDetail { epic_id, story_id } => {
@@ -52,8 +127,23 @@ impl Navigator {
                     .create_epic(epic)
                     .with_context(|| anyhow!("failed to create epic!"))?;
             }
+            Action::UpdateEpic { epic_id } => {
+                let epic = self
+                    .db
+                    .get_epic(epic_id)?
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let epic = (self.prompts.update_epic)(&epic);
+
+                self.db
+                    .update_epic(epic_id, epic.name, epic.description)
+                    .with_context(|| anyhow!("failed to update epic!"))?;
+            }
             Action::UpdateEpicStatus { epic_id } => {
-                let status = (self.prompts.update_status)();
+                let epic = self
+                    .db
+                    .get_epic(epic_id)?
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let status = (self.prompts.update_status)(&self.next_states(&epic.status)?);
 
                 if let Some(status) = status {
                     self.db
@@ -78,10 +168,49 @@ impl Navigator {
                     .create_story(story, epic_id)
                     .with_context(|| anyhow!("failed to create story!"))?;
             }
-            Action::UpdateStoryStatus { story_id } => {
-                let status = (self.prompts.update_status)();
+            Action::UpdateStory { story_id } => {
+                let story = self
+                    .db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                let story = (self.prompts.update_story)(&story);
+
+                self.db
+                    .update_story(story_id, story.name, story.description)
+                    .with_context(|| anyhow!("failed to update story!"))?;
+            }
+            Action::UpdateStoryEstimate { story_id } => {
+                let story = self
+                    .db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                let story = (self.prompts.update_estimate)(&story);
+
+                self.db
+                    .update_story_estimate(story_id, story.priority, story.points)
+                    .with_context(|| anyhow!("failed to update story!"))?;
+            }
+            Action::UpdateStoryStatus { story_id, to } => {
+                let status = match to {
+                    Some(status) => Some(status),
+                    None => {
+                        let story = self
+                            .db
+                            .get_story(story_id)?
+                            .ok_or_else(|| anyhow!("could not find story!"))?;
+                        (self.prompts.update_status)(&self.next_states(&story.status)?)
+                    }
+                };
 
                 if let Some(status) = status {
+                    // starting work that is still waiting on other stories needs a second look
+                    if self.db.get_workflow()?.is_in_progress(&status) {
+                        let blockers = self.db.open_blockers(story_id)?;
+                        if !blockers.is_empty() && !(self.prompts.start_blocked_story)(&blockers) {
+                            return Ok(());
+                        }
+                    }
+
                     self.db
                         .update_story_status(story_id, status)
                         .with_context(|| anyhow!("failed to update story!"))?;
@@ -98,18 +227,461 @@ impl Navigator {
                     }
                 }
             }
+            Action::MoveStory { epic_id, story_id } => {
+                let target_epics = self
+                    .db
+                    .get_epics()?
+                    .into_iter()
+                    .filter(|(id, _)| *id != epic_id)
+                    .sorted_by_key(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                if target_epics.is_empty() {
+                    return Err(anyhow!("there is no other epic to move this story to!"));
+                }
+
+                if let Some(to_epic_id) = (self.prompts.move_story)(&target_epics) {
+                    self.db
+                        .move_story(story_id, epic_id, to_epic_id)
+                        .with_context(|| anyhow!("failed to move story!"))?;
+
+                    // the story page keeps track of its epic, so point it at the new one
+                    self.pages.pop();
+                    self.pages.push(Box::new(StoryDetail {
+                        epic_id: to_epic_id,
+                        story_id,
+                        db: Rc::clone(&self.db),
+                    }));
+                }
+            }
+            Action::AssignEpic { epic_id } => {
+                if let Some((username, new_person)) = self.pick_assignee()? {
+                    self.db
+                        .assign_epic(epic_id, Some(username), new_person)
+                        .with_context(|| anyhow!("failed to assign epic!"))?;
+                }
+            }
+            Action::UnassignEpic { epic_id } => {
+                self.db
+                    .assign_epic(epic_id, None, None)
+                    .with_context(|| anyhow!("failed to unassign epic!"))?;
+            }
+            Action::AssignStory { story_id } => {
+                if let Some((username, new_person)) = self.pick_assignee()? {
+                    self.db
+                        .assign_story(story_id, Some(username), new_person)
+                        .with_context(|| anyhow!("failed to assign story!"))?;
+                }
+            }
+            Action::UnassignStory { story_id } => {
+                self.db
+                    .assign_story(story_id, None, None)
+                    .with_context(|| anyhow!("failed to unassign story!"))?;
+            }
+            Action::NavigateToMyWork => {
+                self.pages.push(Box::new(MyWork {
+                    user: self.user.clone(),
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::NavigateToUpcoming { days } => {
+                // looking further ahead from the upcoming page replaces it
+                if self
+                    .get_current_page()
+                    .is_some_and(|page| page.as_any().is::<Upcoming>())
+                {
+                    self.pages.pop();
+                }
+                self.pages.push(Box::new(Upcoming {
+                    days,
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::UpdateEpicDue { epic_id } => {
+                let epic = self
+                    .db
+                    .get_epic(epic_id)?
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let due = (self.prompts.update_due)(epic.due);
+
+                self.db
+                    .set_epic_due(epic_id, due)
+                    .with_context(|| anyhow!("failed to update epic!"))?;
+            }
+            Action::UpdateStoryDue { story_id } => {
+                let story = self
+                    .db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                let due = (self.prompts.update_due)(story.due);
+
+                self.db
+                    .set_story_due(story_id, due)
+                    .with_context(|| anyhow!("failed to update story!"))?;
+            }
+            Action::AddEpicLabel { epic_id } => {
+                let board_labels = self.label_names(None)?;
+                if let Some(label) = (self.prompts.add_label)(&board_labels) {
+                    self.db
+                        .add_epic_label(epic_id, &label)
+                        .with_context(|| anyhow!("failed to label epic!"))?;
+                }
+            }
+            Action::RemoveEpicLabel { epic_id } => {
+                let epic = self
+                    .db
+                    .get_epic(epic_id)?
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let epic_labels = self.label_names(Some(&epic.labels))?;
+                if epic_labels.is_empty() {
+                    return Err(anyhow!("the epic has no labels to remove!"));
+                }
+                if let Some(label) = (self.prompts.remove_label)(&epic_labels) {
+                    self.db
+                        .remove_epic_label(epic_id, &label)
+                        .with_context(|| anyhow!("failed to unlabel epic!"))?;
+                }
+            }
+            Action::AddStoryLabel { story_id } => {
+                let board_labels = self.label_names(None)?;
+                if let Some(label) = (self.prompts.add_label)(&board_labels) {
+                    self.db
+                        .add_story_label(story_id, &label)
+                        .with_context(|| anyhow!("failed to label story!"))?;
+                }
+            }
+            Action::RemoveStoryLabel { story_id } => {
+                let story = self
+                    .db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                let story_labels = self.label_names(Some(&story.labels))?;
+                if story_labels.is_empty() {
+                    return Err(anyhow!("the story has no labels to remove!"));
+                }
+                if let Some(label) = (self.prompts.remove_label)(&story_labels) {
+                    self.db
+                        .remove_story_label(story_id, &label)
+                        .with_context(|| anyhow!("failed to unlabel story!"))?;
+                }
+            }
+            Action::AddComment { story_id } => {
+                // comments are attributed to the configured user, ask before they type anything
+                let author = self.user.clone().ok_or_else(|| {
+                    anyhow!("set `user` in the my-jira config file or $USER to comment!")
+                })?;
+                if let Some(body) = (self.prompts.add_comment)() {
+                    self.db
+                        .add_comment(story_id, Comment::new(author, body))
+                        .with_context(|| anyhow!("failed to add comment!"))?;
+                }
+            }
+            Action::DeleteComment { story_id } => {
+                let story = self
+                    .db
+                    .get_story(story_id)?
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                if story.comments.is_empty() {
+                    return Err(anyhow!("the story has no comments to delete!"));
+                }
+                if let Some(index) = (self.prompts.delete_comment)(&story.comments) {
+                    self.db
+                        .delete_comment(story_id, &story.comments[index])
+                        .with_context(|| anyhow!("failed to delete comment!"))?;
+                }
+            }
+            Action::AddChecklistItem { story_id } => {
+                if let Some(text) = (self.prompts.add_checklist_item)() {
+                    self.db
+                        .add_checklist_item(story_id, &text)
+                        .with_context(|| anyhow!("failed to add checklist item!"))?;
+                }
+            }
+            Action::ToggleChecklistItem { story_id } => {
+                let checklist = self.checklist(story_id)?;
+                if let Some(index) = (self.prompts.toggle_checklist_item)(&checklist) {
+                    self.db
+                        .toggle_checklist_item(story_id, index)
+                        .with_context(|| anyhow!("failed to update checklist item!"))?;
+                }
+            }
+            Action::RemoveChecklistItem { story_id } => {
+                let checklist = self.checklist(story_id)?;
+                if let Some(index) = (self.prompts.remove_checklist_item)(&checklist) {
+                    self.db
+                        .remove_checklist_item(story_id, index)
+                        .with_context(|| anyhow!("failed to remove checklist item!"))?;
+                }
+            }
+            Action::MoveChecklistItem { story_id } => {
+                let checklist = self.checklist(story_id)?;
+                if let Some((from, to)) = (self.prompts.move_checklist_item)(&checklist) {
+                    self.db
+                        .move_checklist_item(story_id, from, to)
+                        .with_context(|| anyhow!("failed to move checklist item!"))?;
+                }
+            }
+            Action::AddDependency { story_id } => {
+                if let Some(dependency) = (self.prompts.add_dependency)() {
+                    let (story_id, blocker_id) = match dependency {
+                        Dependency::BlockedBy(other_id) => (story_id, other_id),
+                        Dependency::Blocks(other_id) => (other_id, story_id),
+                    };
+                    self.db
+                        .add_blocker(story_id, blocker_id)
+                        .with_context(|| anyhow!("failed to add dependency!"))?;
+                }
+            }
+            Action::RemoveDependency { story_id } => {
+                let dependencies = self.db.get_dependencies(story_id)?;
+                if dependencies.is_empty() {
+                    return Err(anyhow!("the story has no dependencies to remove!"));
+                }
+                if let Some(dependency) = (self.prompts.remove_dependency)(&dependencies) {
+                    let (story_id, blocker_id) = match dependency {
+                        Dependency::BlockedBy(other_id) => (story_id, other_id),
+                        Dependency::Blocks(other_id) => (other_id, story_id),
+                    };
+                    self.db
+                        .remove_blocker(story_id, blocker_id)
+                        .with_context(|| anyhow!("failed to remove dependency!"))?;
+                }
+            }
+            Action::NavigateToSearch { query } => {
+                let Some(query) = query.or_else(|| (self.prompts.search)()) else {
+                    return Ok(());
+                };
+                // searching again from the search page replaces it
+                if self
+                    .get_current_page()
+                    .is_some_and(|page| page.as_any().is::<Search>())
+                {
+                    self.pages.pop();
+                }
+                self.pages.push(Box::new(Search {
+                    query,
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::NavigateToKanban { epic_id } => {
+                self.pages.push(Box::new(Kanban {
+                    epic_id,
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::NavigateToSprint => {
+                self.pages.push(Box::new(SprintPage {
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::AddSprintStory { sprint_id } => {
+                if let Some(story_id) = (self.prompts.add_sprint_story)() {
+                    self.db
+                        .add_sprint_story(sprint_id, story_id)
+                        .with_context(|| anyhow!("failed to add story to sprint!"))?;
+                }
+            }
+            Action::RemoveSprintStory { sprint_id } => {
+                if let Some(story_id) = (self.prompts.remove_sprint_story)() {
+                    self.db
+                        .remove_sprint_story(sprint_id, story_id)
+                        .with_context(|| anyhow!("failed to remove story from sprint!"))?;
+                }
+            }
+            Action::NavigateToComments { story_id } => {
+                self.pages.push(Box::new(Comments {
+                    story_id,
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::NavigateToHistory { item, item_id } => {
+                self.pages.push(Box::new(History {
+                    item,
+                    item_id,
+                    db: Rc::clone(&self.db),
+                }));
+            }
+            Action::FilterEpics { label } => {
+                let view = ListView {
+                    label,
+                    page: 0,
+                    ..self.epics_view.clone()
+                };
+                self.view_epics(view);
+            }
+            Action::FilterStories { epic_id, label } => {
+                let view = ListView {
+                    label,
+                    page: 0,
+                    ..self.story_views.get(&epic_id).cloned().unwrap_or_default()
+                };
+                self.view_stories(epic_id, view);
+            }
+            Action::ViewEpics { view } => self.view_epics(view),
+            Action::ViewStories { epic_id, view } => self.view_stories(epic_id, view),
+            Action::NavigateToBoardSwitcher => {
+                self.pages.push(Box::new(BoardSwitcher {
+                    boards: self.boards.clone(),
+                    current_board: self.current_board.clone(),
+                }));
+            }
+            Action::SwitchBoard { name } => {
+                let board = self
+                    .boards
+                    .iter()
+                    .find(|board| board.name.as_ref() == Some(&name))
+                    .ok_or_else(|| anyhow!("could not find board {}!", name))?;
+
+                let db = JiraDatabase::new(board.backend, board.path.clone(), false)
+                    .with_context(|| anyhow!("failed to open board {}!", name))?
+                    .with_actor(self.db.actor.clone());
+                if let Some(workflow) = &board.workflow {
+                    db.set_workflow(workflow.clone())
+                        .with_context(|| anyhow!("failed to apply the workflow of {}!", name))?;
+                }
+
+                // every page holds on to the previous board, so start over from the home page
+                self.db = Rc::new(db);
+                self.epics_view = ListView::default();
+                self.story_views.clear();
+                self.pages = vec![Box::new(HomePage {
+                    db: Rc::clone(&self.db),
+                    view: ListView::default(),
+                })];
+                self.current_board = Some(name);
+                // the changes belong to the previous board
+                self.undo.clear();
+                self.redo.clear();
+            }
+            Action::Undo => {
+                let revision = self.undo.pop().ok_or_else(|| anyhow!("nothing to undo!"))?;
+                self.db
+                    .undo(&revision)
+                    .with_context(|| anyhow!("failed to undo!"))?;
+                self.redo.push(revision);
+                self.drop_missing_pages()?;
+            }
+            Action::Redo => {
+                let revision = self.redo.pop().ok_or_else(|| anyhow!("nothing to redo!"))?;
+                self.db
+                    .redo(&revision)
+                    .with_context(|| anyhow!("failed to redo!"))?;
+                self.undo.push(revision);
+                self.drop_missing_pages()?;
+            }
             Action::Exit => self.pages.clear(),
         }
 
         Ok(())
     }
 
+    // Undoing or redoing can remove the items pages are showing, so go back to the last
+    // page that still has something to show.
+    fn drop_missing_pages(&mut self) -> Result<()> {
+        let db_state = self.db.read_db()?;
+
+        let is_missing = |page: &dyn Page| {
+            let page = page.as_any();
+            if let Some(epic_detail) = page.downcast_ref::<EpicDetail>() {
+                !db_state.epics.contains_key(&epic_detail.epic_id)
+            } else if let Some(story_detail) = page.downcast_ref::<StoryDetail>() {
+                db_state
+                    .epics
+                    .get(&story_detail.epic_id)
+                    .is_none_or(|epic| !epic.stories.contains(&story_detail.story_id))
+            } else if let Some(comments) = page.downcast_ref::<Comments>() {
+                !db_state.stories.contains_key(&comments.story_id)
+            } else {
+                false
+            }
+        };
+
+        if let Some(index) = self.pages.iter().position(|page| is_missing(page.as_ref())) {
+            self.pages.truncate(index);
+        }
+        Ok(())
+    }
+
+    // Names of the given labels, or of every label on the board, sorted.
+    fn label_names(&self, label_ids: Option<&[u32]>) -> Result<Vec<String>> {
+        let labels = self.db.get_labels()?;
+
+        Ok(labels
+            .into_iter()
+            .filter(|(id, _)| label_ids.is_none_or(|label_ids| label_ids.contains(id)))
+            .map(|(_, label)| label.name)
+            .sorted()
+            .collect())
+    }
+
+    // The checklist of a story, which has to have items for them to be picked.
+    fn checklist(&self, story_id: u32) -> Result<Vec<ChecklistItem>> {
+        let story = self
+            .db
+            .get_story(story_id)?
+            .ok_or_else(|| anyhow!("could not find story!"))?;
+        if story.checklist.is_empty() {
+            return Err(anyhow!("the story's checklist is empty!"));
+        }
+        Ok(story.checklist)
+    }
+
+    // The picked username, along with the person to register if they are new to the board.
+    fn pick_assignee(&self) -> Result<Option<(String, Option<Person>)>> {
+        let people = self
+            .db
+            .get_people()?
+            .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .collect::<Vec<_>>();
+
+        let Some((username, person)) = (self.prompts.assign)(&people) else {
+            return Ok(None);
+        };
+
+        let is_new = !people.iter().any(|(known, _)| *known == username);
+        Ok(Some((username, is_new.then_some(person))))
+    }
+
+    // Changing how a list is shown swaps the current page for a copy of itself using the new view,
+    // which is remembered for the next time the list is opened.
+    fn view_epics(&mut self, view: ListView) {
+        self.epics_view = view.clone();
+        self.pages.pop();
+        self.pages.push(Box::new(HomePage {
+            db: Rc::clone(&self.db),
+            view,
+        }));
+    }
+
+    fn view_stories(&mut self, epic_id: u32, view: ListView) {
+        self.story_views.insert(epic_id, view.clone());
+        self.pages.pop();
+        self.pages.push(Box::new(EpicDetail {
+            epic_id,
+            db: Rc::clone(&self.db),
+            view,
+        }));
+    }
+
+    // The states the board's workflow allows moving to from `status`.
+    fn next_states(&self, status: &Status) -> Result<Vec<Status>> {
+        let workflow = self.db.get_workflow()?;
+        let next_states = workflow.next_states(status);
+
+        if next_states.is_empty() {
+            return Err(anyhow!("the workflow doesn't allow leaving {}!", status));
+        }
+        Ok(next_states.to_vec())
+    }
+
     // Private functions used for testing
 
+    #[cfg(test)]
     fn get_page_count(&self) -> usize {
         self.pages.len()
     }
 
+    #[cfg(test)]
     fn set_prompts(&mut self, prompts: Prompts) {
         self.prompts = prompts;
     }
@@ -118,15 +690,20 @@ impl Navigator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::NaiveDate;
+    use std::collections::BTreeMap;
+
     use crate::{
-        db::test_utils::MockDB,
-        models::{Epic, Status, Story},
+        db::{test_utils::MockDB, Backend},
+        models::{Epic, Person, Priority, SortKey, Status, Story, Workflow},
     };
 
     #[test]
     fn should_start_on_home_page() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
         let nav = Navigator::new(db);
 
@@ -142,6 +719,8 @@ mod tests {
     fn handle_action_should_navigate_pages() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
 
         let mut nav = Navigator::new(db);
@@ -186,10 +765,61 @@ mod tests {
         assert_eq!(nav.get_page_count(), 0);
     }
 
+    #[test]
+    fn handle_action_should_switch_boards() {
+        let dir = tempfile::tempdir().unwrap();
+        let board_path = dir.path().join("other.json").to_str().unwrap().to_owned();
+        std::fs::write(
+            &board_path,
+            r#"{ "last_item_id": 0, "epics": {}, "stories": {} }"#,
+        )
+        .unwrap();
+
+        let boards = vec![Board {
+            name: Some("other".to_owned()),
+            path: board_path,
+            backend: Backend::Json,
+            workflow: None,
+        }];
+
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        db.create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(db).with_boards(boards, None);
+
+        nav.handle_action(Action::NavigateToBoardSwitcher).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let board_switcher = current_page.as_any().downcast_ref::<BoardSwitcher>();
+        assert!(board_switcher.is_some());
+
+        assert!(nav
+            .handle_action(Action::SwitchBoard {
+                name: "missing".to_owned()
+            })
+            .is_err());
+
+        nav.handle_action(Action::SwitchBoard {
+            name: "other".to_owned(),
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        assert_eq!(nav.current_board.as_deref(), Some("other"));
+        assert!(nav.db.read_db().unwrap().epics.is_empty());
+    }
+
     #[test]
     fn handle_action_should_clear_pages_on_exit() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
 
         let mut nav = Navigator::new(db);
@@ -210,6 +840,8 @@ mod tests {
     fn handle_action_should_handle_create_epic() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
 
         let mut nav = Navigator::new(Rc::clone(&db));
@@ -233,6 +865,8 @@ mod tests {
     fn handle_action_should_handle_update_epic() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -241,7 +875,7 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.update_status = Box::new(|_| Some(Status::IN_PROGRESS));
 
         nav.set_prompts(prompts);
 
@@ -251,14 +885,49 @@ mod tests {
         let db_state = db.read_db().unwrap();
         assert_eq!(
             db_state.epics.get(&epic_id).unwrap().status,
-            Status::InProgress
+            Status::IN_PROGRESS
         );
     }
 
+    #[test]
+    fn handle_action_should_handle_edit_epic() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("typo".to_owned(), "description".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_epic = Box::new(|epic| {
+            let mut epic = epic.clone();
+            epic.name = "name".to_owned();
+            epic
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpic { epic_id }).unwrap();
+
+        let epic = db.get_epic(epic_id).unwrap().unwrap();
+        assert_eq!(epic.name, "name");
+        assert_eq!(epic.description, "description");
+
+        assert!(nav
+            .handle_action(Action::UpdateEpic { epic_id: 999 })
+            .is_err());
+    }
+
     #[test]
     fn handle_action_should_handle_delete_epic() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -281,6 +950,8 @@ mod tests {
     fn handle_action_should_handle_create_story() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -307,6 +978,8 @@ mod tests {
     fn handle_action_should_handle_update_story() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -318,25 +991,38 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.update_status = Box::new(|_| Some(Status::IN_PROGRESS));
 
         nav.set_prompts(prompts);
 
-        nav.handle_action(Action::UpdateStoryStatus { story_id })
+        nav.handle_action(Action::UpdateStoryStatus { story_id, to: None })
             .unwrap();
 
         let db_state = db.read_db().unwrap();
         assert_eq!(
             db_state.stories.get(&story_id).unwrap().status,
-            Status::InProgress
+            Status::IN_PROGRESS
         );
     }
 
     #[test]
-    fn handle_action_should_handle_delete_story() {
+    fn handle_action_should_only_offer_next_states_of_the_workflow() {
         let db = Rc::new(JiraDatabase {
             database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
         });
+        let transitions = BTreeMap::from([
+            ("Todo".to_owned(), vec!["Doing".to_owned()]),
+            (
+                "Doing".to_owned(),
+                vec!["Todo".to_owned(), "Done".to_owned()],
+            ),
+        ]);
+        db.set_workflow(
+            Workflow::new(&["Todo", "Doing", "Done"], &["Done"], Some(&transitions)).unwrap(),
+        )
+        .unwrap();
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -346,15 +1032,914 @@ mod tests {
 
         let mut nav = Navigator::new(Rc::clone(&db));
 
+        // picks the last state offered
         let mut prompts = Prompts::new();
-        prompts.delete_story = Box::new(|| true);
+        prompts.update_status = Box::new(|next_states| next_states.last().cloned());
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryStatus { story_id, to: None })
+            .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::new("Doing")
+        );
+
+        nav.handle_action(Action::UpdateStoryStatus { story_id, to: None })
+            .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::new("Done")
+        );
+
+        // done stories can't be reopened in this workflow
+        assert!(nav
+            .handle_action(Action::UpdateStoryStatus { story_id, to: None })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_handle_edit_story() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("name".to_owned(), "typo".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_story = Box::new(|story| {
+            let mut story = story.clone();
+            story.description = "description".to_owned();
+            story
+        });
 
         nav.set_prompts(prompts);
 
-        nav.handle_action(Action::DeleteStory { epic_id, story_id })
+        nav.handle_action(Action::UpdateStory { story_id }).unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.name, "name");
+        assert_eq!(story.description, "description");
+    }
+
+    #[test]
+    fn handle_action_should_handle_update_story_estimate() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
             .unwrap();
 
-        let db_state = db.read_db().unwrap();
-        assert_eq!(db_state.stories.len(), 0);
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_estimate = Box::new(|story| {
+            let mut story = story.clone();
+            story.priority = Priority::High;
+            story.points = Some(5);
+            story
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryEstimate { story_id })
+            .unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.priority, Priority::High);
+        assert_eq!(story.points, Some(5));
+    }
+
+    #[test]
+    fn handle_action_should_handle_move_story() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let from_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let to_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), from_epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.move_story = Box::new(|target_epics| {
+            assert_eq!(target_epics.len(), 1);
+            Some(target_epics[0].0)
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail {
+            epic_id: from_epic_id,
+        })
+        .unwrap();
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: from_epic_id,
+            story_id,
+        })
+        .unwrap();
+        nav.handle_action(Action::MoveStory {
+            epic_id: from_epic_id,
+            story_id,
+        })
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.epics[&from_epic_id].stories.is_empty());
+        assert_eq!(db_state.epics[&to_epic_id].stories, vec![story_id]);
+
+        assert_eq!(nav.get_page_count(), 3);
+        let current_page = nav.get_current_page().unwrap();
+        let story_detail = current_page.as_any().downcast_ref::<StoryDetail>().unwrap();
+        assert_eq!(story_detail.epic_id, to_epic_id);
+    }
+
+    #[test]
+    fn handle_action_should_fail_to_move_a_story_without_other_epics() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.move_story = Box::new(|_| panic!("there is nothing to pick from"));
+        nav.set_prompts(prompts);
+
+        assert!(nav
+            .handle_action(Action::MoveStory { epic_id, story_id })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_handle_assign_story() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.assign = Box::new(|_| {
+            Some((
+                "alice".to_owned(),
+                Person {
+                    name: "Alice".to_owned(),
+                },
+            ))
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::AssignStory { story_id }).unwrap();
+        nav.handle_action(Action::AssignEpic { epic_id }).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.people["alice"].name, "Alice");
+        assert_eq!(
+            db_state.stories[&story_id].assignee.as_deref(),
+            Some("alice")
+        );
+        assert_eq!(db_state.epics[&epic_id].assignee.as_deref(), Some("alice"));
+
+        nav.handle_action(Action::UnassignStory { story_id })
+            .unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().assignee, None);
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_my_work() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+
+        let mut nav = Navigator::new(db).with_user(Some("alice".to_owned()));
+
+        nav.handle_action(Action::NavigateToMyWork).unwrap();
+
+        let current_page = nav.get_current_page().unwrap();
+        let my_work = current_page.as_any().downcast_ref::<MyWork>().unwrap();
+        assert_eq!(my_work.user.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn handle_action_should_handle_story_labels() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_epic_label(epic_id, "backend").unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.add_label = Box::new(|board_labels| {
+            assert_eq!(board_labels, ["backend"]);
+            Some("Backend".to_owned())
+        });
+        prompts.remove_label = Box::new(|story_labels| {
+            assert_eq!(story_labels, ["backend"]);
+            Some("backend".to_owned())
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::AddStoryLabel { story_id })
+            .unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().labels, vec![1]);
+
+        nav.handle_action(Action::RemoveStoryLabel { story_id })
+            .unwrap();
+        assert!(db.get_story(story_id).unwrap().unwrap().labels.is_empty());
+
+        assert!(nav
+            .handle_action(Action::RemoveStoryLabel { story_id })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_handle_comments() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.add_comment = Box::new(|| Some("looks good".to_owned()));
+        prompts.delete_comment = Box::new(|comments| {
+            assert_eq!(comments.len(), 1);
+            Some(0)
+        });
+        nav.set_prompts(prompts);
+
+        // comments need an author
+        assert!(nav.handle_action(Action::AddComment { story_id }).is_err());
+
+        let mut nav = nav.with_user(Some("alice".to_owned()));
+        nav.handle_action(Action::AddComment { story_id }).unwrap();
+
+        let comments = db.get_story(story_id).unwrap().unwrap().comments;
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "alice");
+        assert_eq!(comments[0].body, "looks good");
+
+        nav.handle_action(Action::NavigateToComments { story_id })
+            .unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().downcast_ref::<Comments>().is_some());
+
+        nav.handle_action(Action::DeleteComment { story_id })
+            .unwrap();
+        assert!(db.get_story(story_id).unwrap().unwrap().comments.is_empty());
+
+        assert!(nav
+            .handle_action(Action::DeleteComment { story_id })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_replace_page_when_filtering() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+
+        let mut nav = Navigator::new(db);
+
+        nav.handle_action(Action::FilterEpics {
+            label: Some("backend".to_owned()),
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let home_page = current_page.as_any().downcast_ref::<HomePage>().unwrap();
+        assert_eq!(home_page.view.label.as_deref(), Some("backend"));
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        nav.handle_action(Action::FilterStories {
+            epic_id: 1,
+            label: Some("ui".to_owned()),
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(epic_detail.view.label.as_deref(), Some("ui"));
+    }
+
+    #[test]
+    fn handle_action_should_remember_views_while_navigating() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let sorted = ListView {
+            sort: Some(SortKey::Name),
+            ..ListView::default()
+        };
+        let hiding_closed = ListView {
+            hidden: vec![Status::CLOSED],
+            page: 2,
+            ..ListView::default()
+        };
+
+        let mut nav = Navigator::new(db);
+
+        nav.handle_action(Action::ViewEpics {
+            view: sorted.clone(),
+        })
+        .unwrap();
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        nav.handle_action(Action::ViewStories {
+            epic_id: 1,
+            view: hiding_closed.clone(),
+        })
+        .unwrap();
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let home_page = current_page.as_any().downcast_ref::<HomePage>().unwrap();
+        assert_eq!(home_page.view, sorted);
+
+        // filtering by label keeps the rest of the view
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(epic_detail.view, hiding_closed);
+
+        nav.handle_action(Action::FilterStories {
+            epic_id: 1,
+            label: Some("ui".to_owned()),
+        })
+        .unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(
+            epic_detail.view,
+            ListView {
+                label: Some("ui".to_owned()),
+                page: 0,
+                ..hiding_closed
+            }
+        );
+
+        // other epics start from the usual view
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 2 })
+            .unwrap();
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(epic_detail.view, ListView::default());
+    }
+
+    #[test]
+    fn handle_action_should_handle_delete_story() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.delete_story = Box::new(|| true);
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::DeleteStory { epic_id, story_id })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 0);
+    }
+
+    #[test]
+    fn handle_action_should_undo_and_redo_deleting_an_epic() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids = (0..2)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let before = db.read_db().unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.delete_epic = Box::new(|| true);
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.handle_action(Action::DeleteEpic { epic_id }).unwrap();
+        assert!(db.read_db().unwrap().epics.is_empty());
+
+        nav.handle_action(Action::Undo).unwrap();
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state, before);
+        assert_eq!(db_state.epics[&epic_id].stories, story_ids);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.handle_action(Action::Redo).unwrap();
+        assert!(db.read_db().unwrap().epics.is_empty());
+        assert!(db.read_db().unwrap().stories.is_empty());
+        // the page of the deleted epic is gone
+        assert_eq!(nav.get_page_count(), 1);
+
+        assert!(nav.handle_action(Action::Redo).is_err());
+        nav.handle_action(Action::Undo).unwrap();
+        assert!(nav.handle_action(Action::Undo).is_err());
+    }
+
+    #[test]
+    fn handle_action_should_not_undo_changes_made_by_others_meanwhile() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        // another process creates an epic while the status is being picked
+        let other_process = Rc::clone(&db);
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(move |_| {
+            other_process
+                .database
+                .transaction(Box::new(|transaction| {
+                    let other_epic_id = transaction.next_item_id()?;
+                    transaction
+                        .insert_epic(other_epic_id, Epic::new("other".to_owned(), "".to_owned()))?;
+                    Ok(vec![])
+                }))
+                .unwrap();
+            Some(Status::CLOSED)
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        nav.handle_action(Action::Undo).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics[&epic_id].status, Status::OPEN);
+        assert_eq!(db_state.epics.len(), 2);
+    }
+
+    #[test]
+    fn handle_action_should_forget_redo_after_a_new_change() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(|_| Some(Status::CLOSED));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        nav.handle_action(Action::Undo).unwrap();
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().status, Status::OPEN);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        assert!(nav.handle_action(Action::Redo).is_err());
+
+        // navigating doesn't count as a change
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.handle_action(Action::Undo).unwrap();
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().status, Status::OPEN);
+    }
+
+    #[test]
+    fn handle_input_should_undo_and_redo_from_any_page() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+
+        let mut nav = Navigator::new(db);
+        assert_eq!(nav.handle_input("z").unwrap(), Some(Action::Undo));
+        assert_eq!(nav.handle_input("c").unwrap(), Some(Action::CreateEpic));
+
+        nav.handle_action(Action::NavigateToComments { story_id: 1 })
+            .unwrap();
+        assert_eq!(nav.handle_input("Z").unwrap(), Some(Action::Redo));
+        assert_eq!(
+            nav.handle_input("p").unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+
+    #[test]
+    fn handle_input_should_search_from_any_page() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+
+        let mut nav = Navigator::new(db);
+        // the home page keeps its label filter
+        assert_eq!(
+            nav.handle_input("/label api").unwrap(),
+            Some(Action::FilterEpics {
+                label: Some("api".to_owned())
+            })
+        );
+        assert_eq!(
+            nav.handle_input("/ login page ").unwrap(),
+            Some(Action::NavigateToSearch {
+                query: Some("login page".to_owned())
+            })
+        );
+
+        nav.handle_action(Action::NavigateToComments { story_id: 1 })
+            .unwrap();
+        assert_eq!(
+            nav.handle_input("/").unwrap(),
+            Some(Action::NavigateToSearch { query: None })
+        );
+    }
+
+    #[test]
+    fn handle_action_should_open_a_single_search_page() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+
+        let mut nav = Navigator::new(db);
+
+        let mut prompts = Prompts::new();
+        prompts.search = Box::new(|| Some("login".to_owned()));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToSearch { query: None })
+            .unwrap();
+        nav.handle_action(Action::NavigateToSearch {
+            query: Some("logout".to_owned()),
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let search = current_page.as_any().downcast_ref::<Search>().unwrap();
+        assert_eq!(search.query, "logout");
+    }
+
+    #[test]
+    fn handle_action_should_handle_due_dates() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_due = Box::new(|_| NaiveDate::from_ymd_opt(2026, 11, 1));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicDue { epic_id })
+            .unwrap();
+        nav.handle_action(Action::UpdateStoryDue { story_id })
+            .unwrap();
+
+        let due = NaiveDate::from_ymd_opt(2026, 11, 1);
+        assert_eq!(db.get_epic(epic_id).unwrap().unwrap().due, due);
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().due, due);
+
+        nav.handle_action(Action::NavigateToUpcoming { days: 7 })
+            .unwrap();
+        nav.handle_action(Action::NavigateToUpcoming { days: 30 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let upcoming = current_page.as_any().downcast_ref::<Upcoming>().unwrap();
+        assert_eq!(upcoming.days, 30);
+    }
+
+    #[test]
+    fn handle_action_should_handle_checklists() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_checklist_item(story_id, "first").unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.add_checklist_item = Box::new(|| Some("second".to_owned()));
+        prompts.toggle_checklist_item = Box::new(|_| Some(0));
+        prompts.move_checklist_item = Box::new(|_| Some((1, 0)));
+        prompts.remove_checklist_item = Box::new(|checklist| checklist.len().checked_sub(1));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::AddChecklistItem { story_id })
+            .unwrap();
+        nav.handle_action(Action::MoveChecklistItem { story_id })
+            .unwrap();
+        nav.handle_action(Action::ToggleChecklistItem { story_id })
+            .unwrap();
+
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.checklist[0].text, "second");
+        assert_eq!(story.checklist_progress(), (1, 2));
+
+        nav.handle_action(Action::RemoveChecklistItem { story_id })
+            .unwrap();
+        let story = db.get_story(story_id).unwrap().unwrap();
+        assert_eq!(story.checklist.len(), 1);
+        assert_eq!(story.checklist[0].text, "second");
+
+        nav.handle_action(Action::Undo).unwrap();
+        assert_eq!(db.get_story(story_id).unwrap().unwrap().checklist.len(), 2);
+
+        let empty_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        assert!(nav
+            .handle_action(Action::ToggleChecklistItem { story_id: empty_id })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_handle_dependencies() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let other_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.add_dependency = Box::new(move || Some(Dependency::Blocks(other_id)));
+        prompts.remove_dependency = Box::new(|dependencies| dependencies.first().copied());
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::AddDependency { story_id })
+            .unwrap();
+        assert_eq!(
+            db.get_story(other_id).unwrap().unwrap().blocked_by,
+            vec![story_id]
+        );
+        // the other way around would be a cycle
+        assert!(nav
+            .handle_action(Action::AddDependency { story_id: other_id })
+            .is_err());
+
+        nav.handle_action(Action::RemoveDependency { story_id: other_id })
+            .unwrap();
+        assert!(db.get_dependencies(story_id).unwrap().is_empty());
+
+        assert!(nav
+            .handle_action(Action::RemoveDependency { story_id })
+            .is_err());
+    }
+
+    #[test]
+    fn handle_action_should_move_stories_to_the_given_status_without_asking() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(|_| panic!("the status was given"));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToKanban { epic_id: None })
+            .unwrap();
+        assert!(nav.get_current_page().unwrap().as_any().is::<Kanban>());
+
+        nav.handle_action(Action::UpdateStoryStatus {
+            story_id,
+            to: Some(Status::IN_PROGRESS),
+        })
+        .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::IN_PROGRESS
+        );
+    }
+
+    #[test]
+    fn handle_action_should_plan_the_sprint() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let today = chrono::Local::now().date_naive();
+        let (sprint_id, _) = db.start_sprint("Sprint 1", today, today).unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.add_sprint_story = Box::new(move || Some(story_id));
+        prompts.remove_sprint_story = Box::new(move || Some(story_id));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToSprint).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+        let current_page = nav.get_current_page().unwrap();
+        assert!(current_page.as_any().is::<SprintPage>());
+
+        nav.handle_action(Action::AddSprintStory { sprint_id })
+            .unwrap();
+        assert_eq!(
+            db.get_sprints().unwrap()[&sprint_id].stories,
+            vec![story_id]
+        );
+
+        nav.handle_action(Action::RemoveSprintStory { sprint_id })
+            .unwrap();
+        assert!(db.get_sprints().unwrap()[&sprint_id].stories.is_empty());
+    }
+
+    #[test]
+    fn handle_action_should_warn_before_starting_blocked_stories() {
+        let db = Rc::new(JiraDatabase {
+            database: Box::new(MockDB::new()),
+            actor: None,
+            recorded: Default::default(),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let blocker_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.add_blocker(story_id, blocker_id).unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(|_| Some(Status::IN_PROGRESS));
+        prompts.start_blocked_story = Box::new(|_| false);
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryStatus { story_id, to: None })
+            .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::OPEN
+        );
+
+        // done blockers don't hold anything up
+        db.update_story_status(blocker_id, Status::CLOSED).unwrap();
+        nav.handle_action(Action::UpdateStoryStatus { story_id, to: None })
+            .unwrap();
+        assert_eq!(
+            db.get_story(story_id).unwrap().unwrap().status,
+            Status::IN_PROGRESS
+        );
     }
 }
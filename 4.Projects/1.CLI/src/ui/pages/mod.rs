@@ -1,16 +1,32 @@
 use std::any::Any;
+use std::cmp::Reverse;
 use std::rc::Rc;
 
 use anyhow::anyhow;
 use anyhow::Result;
+use chrono::{Days, Local, NaiveDate};
 use itertools::Itertools;
 
+use crate::config::Board;
 use crate::db::JiraDatabase;
-use crate::models::Action;
+use crate::io_utils::terminal_width;
+use crate::models::{
+    Action, DBState, Dependency, Epic, ItemKind, ListView, PointsSummary, SortKey, Status, Story,
+    Workflow,
+};
 
 mod page_helpers;
 use page_helpers::*;
 
+// How many of the newest comments the story page shows, the rest are on the comments page.
+const LATEST_COMMENTS: usize = 3;
+
+// How many days ahead the upcoming page looks unless told otherwise.
+pub const UPCOMING_DAYS: u32 = 14;
+
+// How many chars of a description the search page shows around a match.
+const SNIPPET_WIDTH: usize = 38;
+
 pub trait Page {
     fn draw_page(&self) -> Result<()>;
     fn handle_input(&self, input: &str) -> Result<Option<Action>>;
@@ -19,39 +35,92 @@ pub trait Page {
 
 pub struct HomePage {
     pub db: Rc<JiraDatabase>,
+    pub view: ListView,
+}
+
+impl HomePage {
+    // The epics the view lets through, in its order.
+    fn epics(&self, workflow: &Workflow) -> Result<Vec<(u32, Epic)>> {
+        let labels = self.db.get_labels()?;
+        let epics = self.db.get_epics()?.into_iter().filter(|(_, epic)| {
+            !self.view.hidden.contains(&epic.status)
+                && self
+                    .view
+                    .label
+                    .as_ref()
+                    .is_none_or(|label| has_label(&labels, &epic.labels, label))
+        });
+
+        Ok(match self.view.sort {
+            None | Some(SortKey::Id) => epics.sorted_by_key(|(id, _)| *id).collect(),
+            Some(SortKey::Name) => epics
+                .sorted_by_key(|(id, epic)| (epic.name.to_lowercase(), *id))
+                .collect(),
+            Some(SortKey::Status) => epics
+                .sorted_by_key(|(id, epic)| (workflow.state_position(&epic.status), *id))
+                .collect(),
+        })
+    }
 }
+
 impl Page for HomePage {
     fn draw_page(&self) -> Result<()> {
         println!("----------------------------- EPICS -----------------------------");
-        println!("     id     |               name               |      status      ");
+        if let Some(label) = &self.view.label {
+            println!("labelled: {} ([/label] to clear)", label);
+        }
 
-        let epics = self.db.read_db()?.epics;
+        let workflow = self.db.get_workflow()?;
+        let today = Local::now().date_naive();
+        let epics = self.epics(&workflow)?;
+        let (epics, page, page_count) = paginate(&epics, self.view.page);
 
-        for id in epics.keys().sorted() {
-            let epic = &epics[id];
+        println!("{}", format_view(&self.view, page, page_count));
+        println!(
+            "     id     |               name               |      status      |     due     "
+        );
+
+        for (id, epic) in epics {
             let id_col = get_column_string(&id.to_string(), 11);
             let name_col = get_column_string(&epic.name, 32);
-            let status_col = get_column_string(&epic.status.to_string(), 17);
-            println!("{} | {} | {}", id_col, name_col, status_col);
+            let status_col = get_column_string(&epic.status.to_string(), 16);
+            let due = format_due(epic.due, workflow.is_done(&epic.status), today);
+            let due_col = get_column_string(&due, 12);
+            println!("{} | {} | {} | {}", id_col, name_col, status_col, due_col);
         }
 
         println!();
         println!();
 
-        println!("[q] quit | [c] create epic | [:id:] navigate to epic");
+        println!(
+            "[q] quit | [c] create epic | [w] my work | [u] upcoming | [s] sprint | [k] kanban board | [B] switch board | [z] undo | [Z] redo | [n] next page | [b] previous page | [/sort id|name|status] sort | [/hide :status:] hide status | [/show] show all | [/:query:] search | [/label :name:] filter by label | [:id:] navigate to epic"
+        );
 
         Ok(())
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        let epics = self.db.read_db()?.epics;
-
         match input {
             "q" => Ok(Some(Action::Exit)),
             "c" => Ok(Some(Action::CreateEpic)),
+            "w" => Ok(Some(Action::NavigateToMyWork)),
+            "u" => Ok(Some(Action::NavigateToUpcoming {
+                days: UPCOMING_DAYS,
+            })),
+            "s" => Ok(Some(Action::NavigateToSprint)),
+            "B" => Ok(Some(Action::NavigateToBoardSwitcher)),
+            "k" => Ok(Some(Action::NavigateToKanban { epic_id: None })),
             input => {
+                if let Some(label) = parse_label_filter(input) {
+                    return Ok(Some(Action::FilterEpics { label }));
+                }
+                let workflow = self.db.get_workflow()?;
+                let (_, _, page_count) = paginate(&self.epics(&workflow)?, self.view.page);
+                if let Some(view) = parse_view_command(input, &self.view, page_count, &workflow) {
+                    return Ok(Some(Action::ViewEpics { view: view? }));
+                }
                 if let Ok(epic_id) = input.parse::<u32>() {
-                    if epics.contains_key(&epic_id) {
+                    if self.db.get_epic(epic_id)?.is_some() {
                         return Ok(Some(Action::NavigateToEpicDetail { epic_id }));
                     }
                 }
@@ -68,6 +137,68 @@ impl Page for HomePage {
 pub struct EpicDetail {
     pub epic_id: u32,
     pub db: Rc<JiraDatabase>,
+    pub view: ListView,
+}
+
+impl EpicDetail {
+    // The stories of the epic the view lets through, in its order. Missing stories have no
+    // labels, so a label filter hides them, otherwise they come last.
+    fn stories<'a>(&self, epic: &Epic, db_state: &'a DBState) -> Vec<(u32, Option<&'a Story>)> {
+        let workflow = &db_state.workflow;
+        let mut stories = vec![];
+        for id in &epic.stories {
+            let story = db_state.stories.get(id);
+            let shown = match (story, &self.view.label) {
+                (Some(story), label) => {
+                    !self.view.hidden.contains(&story.status)
+                        && label
+                            .as_ref()
+                            .is_none_or(|label| has_label(&db_state.labels, &story.labels, label))
+                }
+                (None, label) => label.is_none(),
+            };
+            if shown {
+                stories.push((*id, story));
+            }
+        }
+
+        let stories = stories.into_iter();
+        match self.view.sort {
+            // most urgent first
+            None => stories
+                .sorted_by_key(|(id, story)| {
+                    (
+                        story.is_none(),
+                        story.as_ref().map(|story| story.priority),
+                        *id,
+                    )
+                })
+                .collect(),
+            Some(SortKey::Id) => stories
+                .sorted_by_key(|(id, story)| (story.is_none(), *id))
+                .collect(),
+            Some(SortKey::Name) => stories
+                .sorted_by_key(|(id, story)| {
+                    (
+                        story.is_none(),
+                        story.as_ref().map(|story| story.name.to_lowercase()),
+                        *id,
+                    )
+                })
+                .collect(),
+            Some(SortKey::Status) => stories
+                .sorted_by_key(|(id, story)| {
+                    (
+                        story.is_none(),
+                        story
+                            .as_ref()
+                            .map(|story| workflow.state_position(&story.status)),
+                        *id,
+                    )
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Page for EpicDetail {
@@ -79,55 +210,158 @@ impl Page for EpicDetail {
             .ok_or_else(|| anyhow!("could not find epic!"))?;
 
         println!("------------------------------ EPIC ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
+        println!("  id  |     name     |   description    |     status    |  assignee ");
 
         let id_col = get_column_string(&self.epic_id.to_string(), 5);
         let name_col = get_column_string(&epic.name, 12);
-        let desc_col = get_column_string(&epic.description, 27);
+        let desc_col = get_column_string(&epic.description, 16);
         let status_col = get_column_string(&epic.status.to_string(), 13);
-        println!("{} | {} | {} | {}", id_col, name_col, desc_col, status_col);
+        let assignee_col = get_column_string(epic.assignee.as_deref().unwrap_or("-"), 10);
+        println!(
+            "{} | {} | {} | {} | {}",
+            id_col, name_col, desc_col, status_col, assignee_col
+        );
+
+        let workflow = &db_state.workflow;
+        let today = Local::now().date_naive();
+        println!(
+            "due: {}",
+            format_due(epic.due, workflow.is_done(&epic.status), today)
+        );
+
+        println!(
+            "labels: {}",
+            format_labels(&label_names(&db_state.labels, &epic.labels))
+        );
+
+        let all_stories = epic
+            .stories
+            .iter()
+            .filter_map(|id| db_state.stories.get(id).cloned())
+            .collect::<Vec<_>>();
+
+        let points = PointsSummary::from_stories(&all_stories, workflow);
+        println!(
+            "points: {} total | {} remaining | {} completed",
+            points.total,
+            points.remaining(),
+            points.completed
+        );
 
         println!();
 
         println!("---------------------------- STORIES ----------------------------");
-        println!("     id     |               name               |      status      ");
+        if let Some(label) = &self.view.label {
+            println!("labelled: {} ([/label] to clear)", label);
+        }
 
-        let stories = &db_state.stories;
+        let stories = self.stories(epic, &db_state);
+        let (stories, page, page_count) = paginate(&stories, self.view.page);
 
-        for id in epic.stories.iter().sorted() {
-            let story = &stories[id];
-            let id_col = get_column_string(&id.to_string(), 11);
-            let name_col = get_column_string(&story.name, 32);
-            let status_col = get_column_string(&story.status.to_string(), 17);
-            println!("{} | {} | {}", id_col, name_col, status_col);
+        println!("{}", format_view(&self.view, page, page_count));
+        println!(
+            "  id  |         name         | priority |    status   | points | checklist |  assignee  |     due     "
+        );
+
+        for (id, story) in stories {
+            let id_col = get_column_string(&id.to_string(), 5);
+
+            // a dangling reference shouldn't hide the rest of the epic, `my-jira fsck` can fix it
+            let Some(story) = story else {
+                let name_col = get_column_string("<missing story>", 20);
+                println!("{} | {} | - | - | - | - | - | -", id_col, name_col);
+                continue;
+            };
+
+            let name_col = get_column_string(&story.name, 20);
+            let priority_col = get_column_string(&story.priority.to_string(), 8);
+            let status_col = get_column_string(&story.status.to_string(), 11);
+            let points_col = get_column_string(&format_points(story.points), 6);
+            let checklist_col = get_column_string(&format_progress(story.checklist_progress()), 9);
+            let assignee_col = get_column_string(story.assignee.as_deref().unwrap_or("-"), 10);
+            let due = format_due(story.due, workflow.is_done(&story.status), today);
+            let due_col = get_column_string(&due, 12);
+            println!(
+                "{} | {} | {} | {} | {} | {} | {} | {}",
+                id_col,
+                name_col,
+                priority_col,
+                status_col,
+                points_col,
+                checklist_col,
+                assignee_col,
+                due_col
+            );
         }
 
         println!();
         println!();
 
-        println!("[p] previous | [u] update epic | [d] delete epic | [c] create story | [:id:] navigate to story");
+        println!("[p] previous | [e] edit epic | [u] update epic | [d] delete epic | [a] assign epic | [x] unassign epic | [t] set due date | [l] add label | [L] remove label | [h] history | [z] undo | [Z] redo | [c] create story | [k] kanban board | [n] next page | [b] previous page | [/sort id|name|status] sort | [/hide :status:] hide status | [/show] show all | [/label :name:] filter by label | [:id:] navigate to story");
 
         Ok(())
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        let db_state = self.db.read_db()?;
-        let stories = db_state.stories;
-
         match input {
             "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "e" => Ok(Some(Action::UpdateEpic {
+                epic_id: self.epic_id,
+            })),
             "u" => Ok(Some(Action::UpdateEpicStatus {
                 epic_id: self.epic_id,
             })),
             "d" => Ok(Some(Action::DeleteEpic {
                 epic_id: self.epic_id,
             })),
+            "a" => Ok(Some(Action::AssignEpic {
+                epic_id: self.epic_id,
+            })),
+            "x" => Ok(Some(Action::UnassignEpic {
+                epic_id: self.epic_id,
+            })),
+            "t" => Ok(Some(Action::UpdateEpicDue {
+                epic_id: self.epic_id,
+            })),
+            "l" => Ok(Some(Action::AddEpicLabel {
+                epic_id: self.epic_id,
+            })),
+            "L" => Ok(Some(Action::RemoveEpicLabel {
+                epic_id: self.epic_id,
+            })),
+            "h" => Ok(Some(Action::NavigateToHistory {
+                item: ItemKind::Epic,
+                item_id: self.epic_id,
+            })),
             "c" => Ok(Some(Action::CreateStory {
                 epic_id: self.epic_id,
             })),
+            "k" => Ok(Some(Action::NavigateToKanban {
+                epic_id: Some(self.epic_id),
+            })),
             input => {
+                if let Some(label) = parse_label_filter(input) {
+                    return Ok(Some(Action::FilterStories {
+                        epic_id: self.epic_id,
+                        label,
+                    }));
+                }
+                let db_state = self.db.read_db()?;
+                let epic = db_state
+                    .epics
+                    .get(&self.epic_id)
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let (_, _, page_count) = paginate(&self.stories(epic, &db_state), self.view.page);
+                if let Some(view) =
+                    parse_view_command(input, &self.view, page_count, &db_state.workflow)
+                {
+                    return Ok(Some(Action::ViewStories {
+                        epic_id: self.epic_id,
+                        view: view?,
+                    }));
+                }
                 if let Ok(story_id) = input.parse::<u32>() {
-                    if stories.contains_key(&story_id) {
+                    if db_state.stories.contains_key(&story_id) {
                         return Ok(Some(Action::NavigateToStoryDetail {
                             epic_id: self.epic_id,
                             story_id,
@@ -152,24 +386,95 @@ pub struct StoryDetail {
 
 impl Page for StoryDetail {
     fn draw_page(&self) -> Result<()> {
-        let db_state = self.db.read_db()?;
-        let story = db_state
-            .stories
-            .get(&self.story_id)
+        let story = self
+            .db
+            .get_story(self.story_id)?
             .ok_or_else(|| anyhow!("could not find story!"))?;
 
         println!("------------------------------ STORY ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
+        println!("  id  |     name     |   description    |     status    |  assignee ");
         let id_col = get_column_string(&self.story_id.to_string(), 5);
         let name_col = get_column_string(&story.name, 12);
-        let desc_col = get_column_string(&story.description, 27);
+        let desc_col = get_column_string(&story.description, 16);
         let status_col = get_column_string(&story.status.to_string(), 13);
-        println!("{} | {} | {} | {}", id_col, name_col, desc_col, status_col);
+        let assignee_col = get_column_string(story.assignee.as_deref().unwrap_or("-"), 10);
+        println!(
+            "{} | {} | {} | {} | {}",
+            id_col, name_col, desc_col, status_col, assignee_col
+        );
+        println!(
+            "priority: {} | points: {}",
+            story.priority,
+            format_points(story.points)
+        );
+        let workflow = self.db.get_workflow()?;
+        let today = Local::now().date_naive();
+        println!(
+            "due: {}",
+            format_due(story.due, workflow.is_done(&story.status), today)
+        );
+        let labels = self.db.get_labels()?;
+        println!(
+            "labels: {}",
+            format_labels(&label_names(&labels, &story.labels))
+        );
+
+        println!();
+
+        println!("-------------------------- DEPENDENCIES --------------------------");
+        let dependencies = self.db.get_dependencies(self.story_id)?;
+        for dependency in &dependencies {
+            let (label, other_id) = match dependency {
+                Dependency::BlockedBy(other_id) => ("blocked by", *other_id),
+                Dependency::Blocks(other_id) => ("blocks", *other_id),
+            };
+            let (name, status) = match self.db.get_story(other_id)? {
+                Some(other) => (other.name, other.status.to_string()),
+                None => ("<missing story>".to_owned(), "-".to_owned()),
+            };
+
+            let label_col = get_column_string(label, 10);
+            let id_col = get_column_string(&other_id.to_string(), 5);
+            let name_col = get_column_string(&name, 20);
+            println!("{} | {} | {} | {}", label_col, id_col, name_col, status);
+        }
+        if dependencies.is_empty() {
+            println!("No dependencies.");
+        }
+
+        println!();
+
+        let (done, total) = story.checklist_progress();
+        println!("---------------------------- CHECKLIST ---------------------------");
+        if total > 0 {
+            println!("{}/{} done", done, total);
+        }
+        for (index, item) in story.checklist.iter().enumerate() {
+            println!("{:>3}. {}", index + 1, item);
+        }
+        if total == 0 {
+            println!("No checklist yet.");
+        }
+
+        println!();
+
+        println!("---------------------------- COMMENTS ----------------------------");
+        let skipped = story.comments.len().saturating_sub(LATEST_COMMENTS);
+        if skipped > 0 {
+            let plural = if skipped == 1 { "" } else { "s" };
+            println!("({} older comment{}, [v] to see all)", skipped, plural);
+        }
+        for comment in &story.comments[skipped..] {
+            println!("{}", comment);
+        }
+        if story.comments.is_empty() {
+            println!("No comments yet.");
+        }
 
         println!();
         println!();
 
-        println!("[p] previous | [u] update story | [d] delete story");
+        println!("[p] previous | [e] edit story | [r] set priority & points | [u] update story | [a] assign story | [x] unassign story | [t] set due date | [l] add label | [L] remove label | [k] add checklist item | [K] remove checklist item | [o] toggle checklist item | [O] reorder checklist | [b] add dependency | [B] remove dependency | [n] add comment | [N] delete comment | [v] view all comments | [h] history | [m] move story | [d] delete story | [z] undo | [Z] redo");
 
         Ok(())
     }
@@ -177,8 +482,65 @@ impl Page for StoryDetail {
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
         match input {
             "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "e" => Ok(Some(Action::UpdateStory {
+                story_id: self.story_id,
+            })),
             "u" => Ok(Some(Action::UpdateStoryStatus {
                 story_id: self.story_id,
+                to: None,
+            })),
+            "r" => Ok(Some(Action::UpdateStoryEstimate {
+                story_id: self.story_id,
+            })),
+            "a" => Ok(Some(Action::AssignStory {
+                story_id: self.story_id,
+            })),
+            "x" => Ok(Some(Action::UnassignStory {
+                story_id: self.story_id,
+            })),
+            "t" => Ok(Some(Action::UpdateStoryDue {
+                story_id: self.story_id,
+            })),
+            "l" => Ok(Some(Action::AddStoryLabel {
+                story_id: self.story_id,
+            })),
+            "L" => Ok(Some(Action::RemoveStoryLabel {
+                story_id: self.story_id,
+            })),
+            "k" => Ok(Some(Action::AddChecklistItem {
+                story_id: self.story_id,
+            })),
+            "K" => Ok(Some(Action::RemoveChecklistItem {
+                story_id: self.story_id,
+            })),
+            "o" => Ok(Some(Action::ToggleChecklistItem {
+                story_id: self.story_id,
+            })),
+            "O" => Ok(Some(Action::MoveChecklistItem {
+                story_id: self.story_id,
+            })),
+            "b" => Ok(Some(Action::AddDependency {
+                story_id: self.story_id,
+            })),
+            "B" => Ok(Some(Action::RemoveDependency {
+                story_id: self.story_id,
+            })),
+            "n" => Ok(Some(Action::AddComment {
+                story_id: self.story_id,
+            })),
+            "N" => Ok(Some(Action::DeleteComment {
+                story_id: self.story_id,
+            })),
+            "v" => Ok(Some(Action::NavigateToComments {
+                story_id: self.story_id,
+            })),
+            "h" => Ok(Some(Action::NavigateToHistory {
+                item: ItemKind::Story,
+                item_id: self.story_id,
+            })),
+            "m" => Ok(Some(Action::MoveStory {
+                epic_id: self.epic_id,
+                story_id: self.story_id,
             })),
             "d" => Ok(Some(Action::DeleteStory {
                 epic_id: self.epic_id,
@@ -193,129 +555,1111 @@ impl Page for StoryDetail {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::db::test_utils::MockDB;
-    use crate::models::{Epic, Story};
+pub struct Comments {
+    pub story_id: u32,
+    pub db: Rc<JiraDatabase>,
+}
 
-    mod home_page {
-        use super::*;
+impl Page for Comments {
+    fn draw_page(&self) -> Result<()> {
+        let story = self
+            .db
+            .get_story(self.story_id)?
+            .ok_or_else(|| anyhow!("could not find story!"))?;
 
-        #[test]
-        fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        println!("---------------------------- COMMENTS ----------------------------");
+        println!("Comments on story {} ({})", self.story_id, story.name);
+        println!();
 
-            let page = HomePage { db };
-            assert_eq!(page.draw_page().is_ok(), true);
+        for (index, comment) in story.comments.iter().enumerate() {
+            println!("{:>3}. {}", index + 1, comment);
+        }
+        if story.comments.is_empty() {
+            println!("No comments yet.");
         }
 
-        #[test]
-        fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        println!();
+        println!();
 
-            let page = HomePage { db };
-            assert_eq!(page.handle_input("").is_ok(), true);
+        println!("[p] previous | [n] add comment | [N] delete comment");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "n" => Ok(Some(Action::AddComment {
+                story_id: self.story_id,
+            })),
+            "N" => Ok(Some(Action::DeleteComment {
+                story_id: self.story_id,
+            })),
+            _ => Ok(None),
         }
+    }
 
-        #[test]
-        fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
-            let epic = Epic::new("".to_owned(), "".to_owned());
+pub struct History {
+    pub item: ItemKind,
+    pub item_id: u32,
+    pub db: Rc<JiraDatabase>,
+}
 
-            let epic_id = db.create_epic(epic).unwrap();
+impl Page for History {
+    fn draw_page(&self) -> Result<()> {
+        println!("---------------------------- HISTORY ----------------------------");
+        println!("Changes to {} {}, oldest first", self.item, self.item_id);
+        println!();
 
-            let page = HomePage { db };
+        let events = self.db.get_history(self.item, self.item_id)?;
+        for event in &events {
+            println!("{}", event);
+        }
+        if events.is_empty() {
+            println!("No changes recorded yet.");
+        }
 
-            let q = "q";
-            let c = "c";
-            let valid_epic_id = epic_id.to_string();
-            let invalid_epic_id = "999";
-            let junk_input = "j983f2j";
-            let junk_input_with_valid_prefix = "q983f2j";
-            let input_with_trailing_white_spaces = "q\n";
+        println!();
+        println!();
 
-            assert_eq!(page.handle_input(q).unwrap(), Some(Action::Exit));
-            assert_eq!(page.handle_input(c).unwrap(), Some(Action::CreateEpic));
-            assert_eq!(
-                page.handle_input(&valid_epic_id).unwrap(),
-                Some(Action::NavigateToEpicDetail { epic_id: 1 })
-            );
-            assert_eq!(page.handle_input(invalid_epic_id).unwrap(), None);
-            assert_eq!(page.handle_input(junk_input).unwrap(), None);
-            assert_eq!(
-                page.handle_input(junk_input_with_valid_prefix).unwrap(),
-                None
-            );
-            assert_eq!(
-                page.handle_input(input_with_trailing_white_spaces).unwrap(),
-                None
-            );
+        println!("[p] previous");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            _ => Ok(None),
         }
     }
 
-    mod epic_detail_page {
-        use super::*;
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
-        #[test]
-        fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
-            let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
-                .unwrap();
+pub struct MyWork {
+    pub user: Option<String>,
+    pub db: Rc<JiraDatabase>,
+}
 
-            let page = EpicDetail { epic_id, db };
-            assert_eq!(page.draw_page().is_ok(), true);
+impl MyWork {
+    // (epic id, epic name, story id, story) for every story assigned to the user
+    fn assigned_stories(&self) -> Result<Vec<(u32, String, u32, Story)>> {
+        let Some(user) = &self.user else {
+            return Ok(vec![]);
+        };
+
+        let db_state = self.db.read_db()?;
+        let mut assigned = vec![];
+
+        for epic_id in db_state.epics.keys().sorted() {
+            let epic = &db_state.epics[epic_id];
+            for story_id in &epic.stories {
+                let Some(story) = db_state.stories.get(story_id) else {
+                    continue;
+                };
+                if story.assignee.as_ref() == Some(user) {
+                    assigned.push((*epic_id, epic.name.clone(), *story_id, story.clone()));
+                }
+            }
         }
 
-        #[test]
-        fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
-            let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
-                .unwrap();
+        Ok(assigned)
+    }
+}
 
-            let page = EpicDetail { epic_id, db };
-            assert_eq!(page.handle_input("").is_ok(), true);
-        }
+impl Page for MyWork {
+    fn draw_page(&self) -> Result<()> {
+        println!("---------------------------- MY WORK ----------------------------");
 
-        #[test]
-        fn draw_page_should_throw_error_for_invalid_epic_id() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        let Some(user) = &self.user else {
+            println!("Set `user` in the my-jira config file or $USER to see your stories.");
+            println!();
+            println!();
+            println!("[p] previous");
+            return Ok(());
+        };
 
-            let page = EpicDetail { epic_id: 999, db };
-            assert_eq!(page.draw_page().is_err(), true);
+        println!("Stories assigned to {}", user);
+        println!();
+        println!("       epic      |   id   |           name           |    status    ");
+
+        for (_, epic_name, story_id, story) in self.assigned_stories()? {
+            let epic_col = get_column_string(&epic_name, 16);
+            let id_col = get_column_string(&story_id.to_string(), 6);
+            let name_col = get_column_string(&story.name, 24);
+            let status_col = get_column_string(&story.status.to_string(), 13);
+            println!("{} | {} | {} | {}", epic_col, id_col, name_col, status_col);
         }
 
-        #[test]
-        fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        println!();
+        println!();
 
-            let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
-                .unwrap();
-            let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
-                .unwrap();
+        println!("[p] previous | [:id:] navigate to story");
 
-            let page = EpicDetail { epic_id, db };
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                if let Ok(story_id) = input.parse::<u32>() {
+                    if let Some((epic_id, _, _, _)) = self
+                        .assigned_stories()?
+                        .into_iter()
+                        .find(|(_, _, id, _)| *id == story_id)
+                    {
+                        return Ok(Some(Action::NavigateToStoryDetail { epic_id, story_id }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// Unfinished work with a due date, as listed on the upcoming page.
+struct DueItem {
+    due: NaiveDate,
+    item: ItemKind,
+    id: u32,
+    // the epic itself for epics
+    epic_id: u32,
+    name: String,
+    status: Status,
+}
+
+pub struct Upcoming {
+    pub days: u32,
+    pub db: Rc<JiraDatabase>,
+}
+
+impl Upcoming {
+    // Unfinished epics and stories due within `days` of `today`, overdue ones included,
+    // soonest first.
+    fn due_items(&self, today: NaiveDate) -> Result<Vec<DueItem>> {
+        let last_day = today
+            .checked_add_days(Days::new(self.days.into()))
+            .unwrap_or(NaiveDate::MAX);
+        let db_state = self.db.read_db()?;
+        let mut items = vec![];
+
+        for (epic_id, epic) in &db_state.epics {
+            items.push(DueItem {
+                due: epic.due.unwrap_or(NaiveDate::MAX),
+                item: ItemKind::Epic,
+                id: *epic_id,
+                epic_id: *epic_id,
+                name: epic.name.clone(),
+                status: epic.status.clone(),
+            });
+
+            for story_id in &epic.stories {
+                let Some(story) = db_state.stories.get(story_id) else {
+                    continue;
+                };
+                items.push(DueItem {
+                    due: story.due.unwrap_or(NaiveDate::MAX),
+                    item: ItemKind::Story,
+                    id: *story_id,
+                    epic_id: *epic_id,
+                    name: story.name.clone(),
+                    status: story.status.clone(),
+                });
+            }
+        }
+
+        Ok(items
+            .into_iter()
+            .filter(|item| item.due <= last_day && !db_state.workflow.is_done(&item.status))
+            .sorted_by_key(|item| (item.due, item.id))
+            .collect())
+    }
+}
+
+impl Page for Upcoming {
+    fn draw_page(&self) -> Result<()> {
+        println!("---------------------------- UPCOMING ----------------------------");
+        println!("Unfinished work due in the next {} days", self.days);
+        println!();
+        println!("     due      | type  |   id   |           name           |    status    ");
+
+        let today = Local::now().date_naive();
+        let items = self.due_items(today)?;
+
+        for item in &items {
+            let due_col = get_column_string(&format_due(Some(item.due), false, today), 13);
+            let type_col = get_column_string(&item.item.to_string(), 5);
+            let id_col = get_column_string(&item.id.to_string(), 6);
+            let name_col = get_column_string(&item.name, 24);
+            let status_col = get_column_string(&item.status.to_string(), 13);
+            println!(
+                "{} | {} | {} | {} | {}",
+                due_col, type_col, id_col, name_col, status_col
+            );
+        }
+        if items.is_empty() {
+            println!("Nothing is due.");
+        }
+
+        println!();
+        println!();
+
+        println!("[p] previous | [:n:d] look n days ahead | [:id:] navigate to epic or story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                if let Some(Ok(days)) = input.strip_suffix('d').map(str::parse::<u32>) {
+                    return Ok(Some(Action::NavigateToUpcoming { days }));
+                }
+                if let Ok(id) = input.parse::<u32>() {
+                    let today = Local::now().date_naive();
+                    if let Some(item) = self
+                        .due_items(today)?
+                        .into_iter()
+                        .find(|item| item.id == id)
+                    {
+                        return Ok(Some(match item.item {
+                            ItemKind::Epic => Action::NavigateToEpicDetail { epic_id: id },
+                            ItemKind::Story => Action::NavigateToStoryDetail {
+                                epic_id: item.epic_id,
+                                story_id: id,
+                            },
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct Card {
+    id: u32,
+    epic_id: u32,
+    name: String,
+    status: Status,
+}
+
+pub struct Kanban {
+    // `None` puts the stories of every epic on the board
+    pub epic_id: Option<u32>,
+    pub db: Rc<JiraDatabase>,
+}
+
+impl Kanban {
+    fn cards(&self) -> Result<Vec<Card>> {
+        let db_state = self.db.read_db()?;
+        let mut cards = vec![];
+
+        for (epic_id, epic) in &db_state.epics {
+            if self.epic_id.is_some_and(|id| id != *epic_id) {
+                continue;
+            }
+            for story_id in &epic.stories {
+                if let Some(story) = db_state.stories.get(story_id) {
+                    cards.push(Card {
+                        id: *story_id,
+                        epic_id: *epic_id,
+                        name: story.name.clone(),
+                        status: story.status.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(cards.into_iter().sorted_by_key(|card| card.id).collect())
+    }
+
+    // Moves a story `step` columns to the right, or to the left for a negative step, as long as
+    // the workflow allows going there.
+    fn move_card(&self, story_id: u32, step: isize) -> Result<Option<Action>> {
+        let Some(card) = self.cards()?.into_iter().find(|card| card.id == story_id) else {
+            return Ok(None);
+        };
+        let workflow = self.db.get_workflow()?;
+        let column = workflow
+            .states
+            .iter()
+            .position(|state| *state == card.status)
+            .ok_or_else(|| anyhow!("story {} is in no column of the board!", story_id))?;
+        let to = column
+            .checked_add_signed(step)
+            .and_then(|column| workflow.states.get(column))
+            .ok_or_else(|| anyhow!("story {} can't move further that way!", story_id))?;
+
+        workflow.check_transition(&card.status, to)?;
+        Ok(Some(Action::UpdateStoryStatus {
+            story_id,
+            to: Some(to.clone()),
+        }))
+    }
+}
+
+impl Page for Kanban {
+    fn draw_page(&self) -> Result<()> {
+        println!("----------------------------- BOARD -----------------------------");
+        match self.epic_id {
+            Some(epic_id) => {
+                let epic = self
+                    .db
+                    .get_epic(epic_id)?
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                println!("Stories of {}", epic.name);
+            }
+            None => println!("Stories of every epic"),
+        }
+        println!();
+
+        let workflow = self.db.get_workflow()?;
+        let cards = self.cards()?;
+        let columns = workflow
+            .states
+            .iter()
+            .map(|state| {
+                cards
+                    .iter()
+                    .filter(|card| card.status == *state)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let width = column_width(terminal_width(), columns.len());
+
+        let header = workflow
+            .states
+            .iter()
+            .zip(&columns)
+            .map(|(state, cards)| get_column_string(&format!("{} ({})", state, cards.len()), width))
+            .join(" | ");
+        println!("{}", header);
+        println!("{}", vec!["-".repeat(width); columns.len()].join("-+-"));
+
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        for row in 0..rows {
+            let line = columns
+                .iter()
+                .map(|cards| {
+                    let card = cards
+                        .get(row)
+                        .map(|card| format!("{} {}", card.id, card.name))
+                        .unwrap_or_default();
+                    get_column_string(&card, width)
+                })
+                .join(" | ");
+            println!("{}", line.trim_end());
+        }
+
+        // `my-jira fsck` reports stories in states the workflow doesn't have
+        let off_board = cards
+            .iter()
+            .filter(|card| !workflow.contains(&card.status))
+            .count();
+        if off_board > 0 {
+            println!();
+            println!(
+                "{} stories are in states the workflow doesn't have",
+                off_board
+            );
+        }
+
+        println!();
+        println!();
+
+        println!("[p] previous | [>:id:] move story right | [<:id:] move story left | [:id:] navigate to story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                if let Some(Ok(story_id)) = input.strip_prefix('>').map(str::parse::<u32>) {
+                    return self.move_card(story_id, 1);
+                }
+                if let Some(Ok(story_id)) = input.strip_prefix('<').map(str::parse::<u32>) {
+                    return self.move_card(story_id, -1);
+                }
+                if let Ok(story_id) = input.parse::<u32>() {
+                    if let Some(card) = self.cards()?.into_iter().find(|card| card.id == story_id) {
+                        return Ok(Some(Action::NavigateToStoryDetail {
+                            epic_id: card.epic_id,
+                            story_id,
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+struct SearchResult {
+    item: ItemKind,
+    id: u32,
+    epic_id: u32,
+    name: String,
+    snippet: String,
+    score: u32,
+}
+
+pub struct Search {
+    pub query: String,
+    pub db: Rc<JiraDatabase>,
+}
+
+impl Search {
+    // Epics and stories whose name or description contains every word of the query, best
+    // matches first. Matches in names count more than in descriptions, and matches at the
+    // start of a word more than inside one.
+    fn results(&self) -> Result<Vec<SearchResult>> {
+        let db_state = self.db.read_db()?;
+        let mut results = vec![];
+
+        for (epic_id, epic) in &db_state.epics {
+            if let Some((score, snippet)) = self.rank(&epic.name, &epic.description) {
+                results.push(SearchResult {
+                    item: ItemKind::Epic,
+                    id: *epic_id,
+                    epic_id: *epic_id,
+                    name: epic.name.clone(),
+                    snippet,
+                    score,
+                });
+            }
+
+            for story_id in &epic.stories {
+                let Some(story) = db_state.stories.get(story_id) else {
+                    continue;
+                };
+                if let Some((score, snippet)) = self.rank(&story.name, &story.description) {
+                    results.push(SearchResult {
+                        item: ItemKind::Story,
+                        id: *story_id,
+                        epic_id: *epic_id,
+                        name: story.name.clone(),
+                        snippet,
+                        score,
+                    });
+                }
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .sorted_by_key(|result| (Reverse(result.score), result.id))
+            .collect())
+    }
+
+    // The score of an item and the snippet showing the first match, preferably in the
+    // description as the name is shown anyway. `None` if a word of the query is missing.
+    fn rank(&self, name: &str, description: &str) -> Option<(u32, String)> {
+        let mut score = 0;
+        let mut name_match = None;
+        let mut description_match = None;
+
+        for term in self.query.split_whitespace() {
+            let in_name = find_term(name, term);
+            let in_description = find_term(description, term);
+            score += match (in_name, in_description) {
+                (Some((_, true)), _) => 10,
+                (Some(_), _) => 6,
+                (None, Some((_, true))) => 3,
+                (None, Some(_)) => 2,
+                (None, None) => return None,
+            };
+
+            let len = term.chars().count();
+            name_match = name_match.or(in_name.map(|(start, _)| (start, len)));
+            description_match = description_match.or(in_description.map(|(start, _)| (start, len)));
+        }
+        if name.trim().eq_ignore_ascii_case(self.query.trim()) {
+            score += 20;
+        }
+
+        let (text, (start, len)) = match description_match {
+            Some(description_match) => (description, description_match),
+            None => (name, name_match?),
+        };
+        Some((score, match_snippet(text, start, len, SNIPPET_WIDTH)))
+    }
+}
+
+impl Page for Search {
+    fn draw_page(&self) -> Result<()> {
+        println!("----------------------------- SEARCH -----------------------------");
+        println!("Epics and stories matching {:?}", self.query);
+        println!();
+        println!(" type  |   id   |         name         |                 match                 ");
+
+        let results = self.results()?;
+
+        for result in &results {
+            let type_col = get_column_string(&result.item.to_string(), 5);
+            let id_col = get_column_string(&result.id.to_string(), 6);
+            let name_col = get_column_string(&result.name, 20);
+            let snippet_col = get_column_string(&result.snippet, SNIPPET_WIDTH);
+            println!("{} | {} | {} | {}", type_col, id_col, name_col, snippet_col);
+        }
+        if results.is_empty() {
+            println!("Nothing matches.");
+        }
+
+        println!();
+        println!();
+
+        println!("[p] previous | [/:query:] search again | [:id:] navigate to epic or story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                if let Ok(id) = input.parse::<u32>() {
+                    if let Some(result) = self.results()?.into_iter().find(|result| result.id == id)
+                    {
+                        return Ok(Some(match result.item {
+                            ItemKind::Epic => Action::NavigateToEpicDetail { epic_id: id },
+                            ItemKind::Story => Action::NavigateToStoryDetail {
+                                epic_id: result.epic_id,
+                                story_id: id,
+                            },
+                        }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct SprintPage {
+    pub db: Rc<JiraDatabase>,
+}
+
+impl SprintPage {
+    // The epic each story of the board belongs to, to navigate from the sprint to a story.
+    fn story_epic(&self, story_id: u32) -> Result<Option<u32>> {
+        Ok(self
+            .db
+            .get_epics()?
+            .into_iter()
+            .find(|(_, epic)| epic.stories.contains(&story_id))
+            .map(|(epic_id, _)| epic_id))
+    }
+}
+
+impl Page for SprintPage {
+    fn draw_page(&self) -> Result<()> {
+        println!("----------------------------- SPRINT -----------------------------");
+
+        let Some((sprint_id, sprint)) = self.db.active_sprint()? else {
+            println!("No active sprint, start one with `my-jira sprint start`");
+            println!();
+            println!();
+            println!("[p] previous");
+            return Ok(());
+        };
+
+        let today = Local::now().date_naive();
+        let days_left = (sprint.end - today).num_days().max(0);
+        println!(
+            "{} | {} to {} | {} days left",
+            sprint.name, sprint.start, sprint.end, days_left
+        );
+
+        let workflow = self.db.get_workflow()?;
+        let all_stories = self.db.read_db()?.stories;
+        let stories = sprint
+            .stories
+            .iter()
+            .filter_map(|id| Some((*id, all_stories.get(id)?)))
+            .collect::<Vec<_>>();
+
+        let points =
+            PointsSummary::from_stories(stories.iter().map(|(_, story)| *story), &workflow);
+        println!(
+            "points: {} total | {} remaining | {} completed",
+            points.total,
+            points.remaining(),
+            points.completed
+        );
+
+        // stories grouped by the workflow's state order, ones in states it doesn't know last
+        for (status, group) in &stories
+            .iter()
+            .sorted_by_key(|(id, story)| (workflow.state_position(&story.status), *id))
+            .chunk_by(|(_, story)| story.status.clone())
+        {
+            println!();
+            println!("{}", status);
+            println!("  id  |         name         | points |  assignee  ");
+            for (id, story) in group {
+                let id_col = get_column_string(&id.to_string(), 5);
+                let name_col = get_column_string(&story.name, 20);
+                let points_col = get_column_string(&format_points(story.points), 6);
+                let assignee_col = get_column_string(story.assignee.as_deref().unwrap_or("-"), 10);
+                println!(
+                    "{} | {} | {} | {}",
+                    id_col, name_col, points_col, assignee_col
+                );
+            }
+        }
+        if stories.is_empty() {
+            println!();
+            println!("No stories planned yet.");
+        }
+
+        println!();
+        print!("{}", self.db.burndown(sprint_id, today)?);
+
+        println!();
+        println!();
+
+        println!("[p] previous | [a] add story | [r] remove story | [:id:] navigate to story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let Some((sprint_id, sprint)) = self.db.active_sprint()? else {
+            return Ok(match input {
+                "p" => Some(Action::NavigateToPreviousPage),
+                _ => None,
+            });
+        };
+
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "a" => Ok(Some(Action::AddSprintStory { sprint_id })),
+            "r" => Ok(Some(Action::RemoveSprintStory { sprint_id })),
+            input => {
+                if let Ok(story_id) = input.parse::<u32>() {
+                    if sprint.stories.contains(&story_id) {
+                        if let Some(epic_id) = self.story_epic(story_id)? {
+                            return Ok(Some(Action::NavigateToStoryDetail { epic_id, story_id }));
+                        }
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct BoardSwitcher {
+    pub boards: Vec<Board>,
+    pub current_board: Option<String>,
+}
+
+impl Page for BoardSwitcher {
+    fn draw_page(&self) -> Result<()> {
+        println!("----------------------------- BOARDS -----------------------------");
+        println!("  #  |       name       | backend |               path               ");
+
+        for (index, board) in self.boards.iter().enumerate() {
+            let marker = if board.name == self.current_board {
+                "*"
+            } else {
+                " "
+            };
+            let index_col = get_column_string(&format!("{}{}", marker, index + 1), 4);
+            let name_col = get_column_string(board.display_name(), 16);
+            let backend_col = get_column_string(&format!("{:?}", board.backend), 7);
+            let path_col = get_column_string(&board.path, 33);
+            println!(
+                "{} | {} | {} | {}",
+                index_col, name_col, backend_col, path_col
+            );
+        }
+
+        if self.boards.is_empty() {
+            println!("No boards configured, add some to the my-jira config file.");
+        }
+
+        println!();
+        println!();
+
+        println!("[p] previous | [:#:] switch to board");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                if let Ok(index) = input.parse::<usize>() {
+                    if let Some(name) = index
+                        .checked_sub(1)
+                        .and_then(|index| self.boards.get(index))
+                        .and_then(|board| board.name.clone())
+                    {
+                        return Ok(Some(Action::SwitchBoard { name }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_utils::MockDB;
+    use crate::db::WholeStateDatabase;
+    use crate::models::{Epic, Story};
+
+    mod home_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = HomePage {
+                db,
+                view: ListView::default(),
+            };
+            assert_eq!(page.draw_page().is_ok(), true);
+        }
+
+        #[test]
+        fn handle_input_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = HomePage {
+                db,
+                view: ListView::default(),
+            };
+            assert_eq!(page.handle_input("").is_ok(), true);
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic = Epic::new("".to_owned(), "".to_owned());
+
+            let epic_id = db.create_epic(epic).unwrap();
+
+            let page = HomePage {
+                db,
+                view: ListView::default(),
+            };
+
+            let q = "q";
+            let c = "c";
+            let valid_epic_id = epic_id.to_string();
+            let invalid_epic_id = "999";
+            let junk_input = "j983f2j";
+            let junk_input_with_valid_prefix = "q983f2j";
+            let input_with_trailing_white_spaces = "q\n";
+
+            assert_eq!(page.handle_input(q).unwrap(), Some(Action::Exit));
+            assert_eq!(page.handle_input(c).unwrap(), Some(Action::CreateEpic));
+            assert_eq!(
+                page.handle_input("B").unwrap(),
+                Some(Action::NavigateToBoardSwitcher)
+            );
+            // a single page, so there is nowhere to go
+            assert_eq!(
+                page.handle_input("n").unwrap(),
+                Some(Action::ViewEpics {
+                    view: ListView::default()
+                })
+            );
+            assert_eq!(
+                page.handle_input("/sort name").unwrap(),
+                Some(Action::ViewEpics {
+                    view: ListView {
+                        sort: Some(SortKey::Name),
+                        ..ListView::default()
+                    }
+                })
+            );
+            assert!(page.handle_input("/hide nope").is_err());
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::NavigateToMyWork)
+            );
+            assert_eq!(
+                page.handle_input("s").unwrap(),
+                Some(Action::NavigateToSprint)
+            );
+            assert_eq!(
+                page.handle_input("k").unwrap(),
+                Some(Action::NavigateToKanban { epic_id: None })
+            );
+            assert_eq!(
+                page.handle_input("u").unwrap(),
+                Some(Action::NavigateToUpcoming {
+                    days: UPCOMING_DAYS
+                })
+            );
+            assert_eq!(
+                page.handle_input("/label backend").unwrap(),
+                Some(Action::FilterEpics {
+                    label: Some("backend".to_owned())
+                })
+            );
+            assert_eq!(
+                page.handle_input("/label").unwrap(),
+                Some(Action::FilterEpics { label: None })
+            );
+            assert_eq!(
+                page.handle_input(&valid_epic_id).unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+            );
+            assert_eq!(page.handle_input(invalid_epic_id).unwrap(), None);
+            assert_eq!(page.handle_input(junk_input).unwrap(), None);
+            assert_eq!(
+                page.handle_input(junk_input_with_valid_prefix).unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(input_with_trailing_white_spaces).unwrap(),
+                None
+            );
+        }
+    }
+
+    mod epic_detail_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                view: ListView::default(),
+            };
+            assert_eq!(page.draw_page().is_ok(), true);
+        }
+
+        #[test]
+        fn handle_input_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                view: ListView::default(),
+            };
+            assert_eq!(page.handle_input("").is_ok(), true);
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error_for_missing_story() {
+            let mock_db = MockDB::new();
+            let mut epic = Epic::new("".to_owned(), "".to_owned());
+            epic.stories = vec![999];
+            let mut db_state = mock_db.read_db().unwrap();
+            db_state.last_item_id = 1;
+            db_state.epics.insert(1, epic);
+            mock_db.write_db(&db_state).unwrap();
+
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(mock_db),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = EpicDetail {
+                epic_id: 1,
+                db,
+                view: ListView::default(),
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error_with_label_filter() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.add_story_label(story_id, "backend").unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                view: ListView {
+                    label: Some("Backend".to_owned()),
+                    ..ListView::default()
+                },
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn stories_should_follow_the_view() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            for name in ["b", "C", "a"] {
+                db.create_story(Story::new(name.to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+            }
+            db.update_story_status(2, Status::CLOSED).unwrap();
+            db.update_story_status(3, Status::IN_PROGRESS).unwrap();
+            let db_state = db.read_db().unwrap();
+            let epic = &db_state.epics[&epic_id];
+
+            let story_ids = |view: ListView| {
+                let page = EpicDetail {
+                    epic_id,
+                    db: Rc::clone(&db),
+                    view,
+                };
+                page.stories(epic, &db_state)
+                    .iter()
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                story_ids(ListView {
+                    sort: Some(SortKey::Name),
+                    ..ListView::default()
+                }),
+                vec![4, 2, 3]
+            );
+            assert_eq!(
+                story_ids(ListView {
+                    sort: Some(SortKey::Status),
+                    ..ListView::default()
+                }),
+                vec![4, 3, 2]
+            );
+            assert_eq!(
+                story_ids(ListView {
+                    hidden: vec![Status::CLOSED],
+                    ..ListView::default()
+                }),
+                vec![3, 4]
+            );
+        }
+
+        #[test]
+        fn draw_page_should_throw_error_for_invalid_epic_id() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = EpicDetail {
+                epic_id: 999,
+                db,
+                view: ListView::default(),
+            };
+            assert_eq!(page.draw_page().is_err(), true);
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                view: ListView::default(),
+            };
 
             let p = "p";
+            let e = "e";
             let u = "u";
             let d = "d";
             let c = "c";
@@ -328,6 +1672,10 @@ mod tests {
                 page.handle_input(p).unwrap(),
                 Some(Action::NavigateToPreviousPage)
             );
+            assert_eq!(
+                page.handle_input(e).unwrap(),
+                Some(Action::UpdateEpic { epic_id: 1 })
+            );
             assert_eq!(
                 page.handle_input(u).unwrap(),
                 Some(Action::UpdateEpicStatus { epic_id: 1 })
@@ -336,58 +1684,392 @@ mod tests {
                 page.handle_input(d).unwrap(),
                 Some(Action::DeleteEpic { epic_id: 1 })
             );
+            assert_eq!(
+                page.handle_input("a").unwrap(),
+                Some(Action::AssignEpic { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("x").unwrap(),
+                Some(Action::UnassignEpic { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("t").unwrap(),
+                Some(Action::UpdateEpicDue { epic_id: 1 })
+            );
             assert_eq!(
                 page.handle_input(c).unwrap(),
                 Some(Action::CreateStory { epic_id: 1 })
             );
             assert_eq!(
-                page.handle_input(&story_id.to_string()).unwrap(),
-                Some(Action::NavigateToStoryDetail {
-                    epic_id: 1,
-                    story_id: 2
-                })
+                page.handle_input("k").unwrap(),
+                Some(Action::NavigateToKanban { epic_id: Some(1) })
+            );
+            assert_eq!(
+                page.handle_input("l").unwrap(),
+                Some(Action::AddEpicLabel { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("L").unwrap(),
+                Some(Action::RemoveEpicLabel { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("h").unwrap(),
+                Some(Action::NavigateToHistory {
+                    item: ItemKind::Epic,
+                    item_id: 1
+                })
+            );
+            assert_eq!(
+                page.handle_input("/label ui").unwrap(),
+                Some(Action::FilterStories {
+                    epic_id: 1,
+                    label: Some("ui".to_owned())
+                })
+            );
+            assert_eq!(
+                page.handle_input(&story_id.to_string()).unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 1,
+                    story_id: 2
+                })
+            );
+            assert_eq!(page.handle_input(invalid_story_id).unwrap(), None);
+            assert_eq!(page.handle_input(junk_input).unwrap(), None);
+            assert_eq!(
+                page.handle_input(junk_input_with_valid_prefix).unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(input_with_trailing_white_spaces).unwrap(),
+                None
+            );
+        }
+    }
+
+    mod story_detail_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoryDetail {
+                epic_id,
+                story_id,
+                db,
+            };
+            assert_eq!(page.draw_page().is_ok(), true);
+        }
+
+        #[test]
+        fn handle_input_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoryDetail {
+                epic_id,
+                story_id,
+                db,
+            };
+            assert_eq!(page.handle_input("").is_ok(), true);
+        }
+
+        #[test]
+        fn draw_page_should_throw_error_for_invalid_story_id() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let _ = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoryDetail {
+                epic_id,
+                story_id: 999,
+                db,
+            };
+            assert_eq!(page.draw_page().is_err(), true);
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoryDetail {
+                epic_id,
+                story_id,
+                db,
+            };
+
+            let p = "p";
+            let e = "e";
+            let u = "u";
+            let m = "m";
+            let d = "d";
+            let some_number = "1";
+            let junk_input = "j983f2j";
+            let junk_input_with_valid_prefix = "p983f2j";
+            let input_with_trailing_white_spaces = "p\n";
+
+            assert_eq!(
+                page.handle_input(p).unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input(e).unwrap(),
+                Some(Action::UpdateStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input(u).unwrap(),
+                Some(Action::UpdateStoryStatus { story_id, to: None })
+            );
+            assert_eq!(
+                page.handle_input(m).unwrap(),
+                Some(Action::MoveStory { epic_id, story_id })
+            );
+            assert_eq!(
+                page.handle_input("r").unwrap(),
+                Some(Action::UpdateStoryEstimate { story_id })
+            );
+            assert_eq!(
+                page.handle_input("a").unwrap(),
+                Some(Action::AssignStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input("x").unwrap(),
+                Some(Action::UnassignStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input("t").unwrap(),
+                Some(Action::UpdateStoryDue { story_id })
+            );
+            assert_eq!(
+                page.handle_input("k").unwrap(),
+                Some(Action::AddChecklistItem { story_id })
+            );
+            assert_eq!(
+                page.handle_input("K").unwrap(),
+                Some(Action::RemoveChecklistItem { story_id })
+            );
+            assert_eq!(
+                page.handle_input("o").unwrap(),
+                Some(Action::ToggleChecklistItem { story_id })
+            );
+            assert_eq!(
+                page.handle_input("O").unwrap(),
+                Some(Action::MoveChecklistItem { story_id })
+            );
+            assert_eq!(
+                page.handle_input("b").unwrap(),
+                Some(Action::AddDependency { story_id })
+            );
+            assert_eq!(
+                page.handle_input("B").unwrap(),
+                Some(Action::RemoveDependency { story_id })
+            );
+            assert_eq!(
+                page.handle_input("l").unwrap(),
+                Some(Action::AddStoryLabel { story_id })
+            );
+            assert_eq!(
+                page.handle_input("L").unwrap(),
+                Some(Action::RemoveStoryLabel { story_id })
+            );
+            assert_eq!(
+                page.handle_input("n").unwrap(),
+                Some(Action::AddComment { story_id })
+            );
+            assert_eq!(
+                page.handle_input("N").unwrap(),
+                Some(Action::DeleteComment { story_id })
+            );
+            assert_eq!(
+                page.handle_input("v").unwrap(),
+                Some(Action::NavigateToComments { story_id })
+            );
+            assert_eq!(
+                page.handle_input("h").unwrap(),
+                Some(Action::NavigateToHistory {
+                    item: ItemKind::Story,
+                    item_id: story_id
+                })
+            );
+            assert_eq!(
+                page.handle_input(d).unwrap(),
+                Some(Action::DeleteStory { epic_id, story_id })
+            );
+            assert_eq!(page.handle_input(some_number).unwrap(), None);
+            assert_eq!(page.handle_input(junk_input).unwrap(), None);
+            assert_eq!(
+                page.handle_input(junk_input_with_valid_prefix).unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(input_with_trailing_white_spaces).unwrap(),
+                None
+            );
+        }
+    }
+
+    mod comments_page {
+        use super::*;
+        use crate::models::Comment;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            for body in ["one", "two", "three", "four"] {
+                db.add_comment(story_id, Comment::new("alice".to_owned(), body.to_owned()))
+                    .unwrap();
+            }
+
+            let page = Comments {
+                story_id,
+                db: Rc::clone(&db),
+            };
+            assert!(page.draw_page().is_ok());
+
+            // the story page only shows the newest comments
+            let page = StoryDetail {
+                epic_id,
+                story_id,
+                db,
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = Comments { story_id: 2, db };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
             );
-            assert_eq!(page.handle_input(invalid_story_id).unwrap(), None);
-            assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
-                page.handle_input(junk_input_with_valid_prefix).unwrap(),
-                None
+                page.handle_input("n").unwrap(),
+                Some(Action::AddComment { story_id: 2 })
             );
             assert_eq!(
-                page.handle_input(input_with_trailing_white_spaces).unwrap(),
-                None
+                page.handle_input("N").unwrap(),
+                Some(Action::DeleteComment { story_id: 2 })
             );
+            assert_eq!(page.handle_input("1").unwrap(), None);
         }
     }
 
-    mod story_detail_page {
+    mod history_page {
         use super::*;
 
         #[test]
         fn draw_page_should_not_throw_error() {
             let db = Rc::new(JiraDatabase {
                 database: Box::new(MockDB::new()),
+                actor: Some("alice".to_owned()),
+                recorded: Default::default(),
             });
-
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
                 .unwrap();
-            let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
-                .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id,
+            let page = History {
+                item: ItemKind::Epic,
+                item_id: epic_id,
+                db: Rc::clone(&db),
+            };
+            assert!(page.draw_page().is_ok());
+
+            let page = History {
+                item: ItemKind::Story,
+                item_id: 999,
                 db,
             };
-            assert_eq!(page.draw_page().is_ok(), true);
+            assert!(page.draw_page().is_ok());
         }
 
         #[test]
-        fn handle_input_should_not_throw_error() {
+        fn handle_input_should_return_the_correct_actions() {
+            let page = History {
+                item: ItemKind::Epic,
+                item_id: 1,
+                db: Rc::new(JiraDatabase {
+                    database: Box::new(MockDB::new()),
+                    actor: None,
+                    recorded: Default::default(),
+                }),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(page.handle_input("1").unwrap(), None);
+        }
+    }
+
+    mod my_work_page {
+        use super::*;
+        use crate::models::Person;
+
+        fn db_with_assigned_story() -> Rc<JiraDatabase> {
             let db = Rc::new(JiraDatabase {
                 database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
             });
 
             let epic_id = db
@@ -396,85 +2078,487 @@ mod tests {
             let story_id = db
                 .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
+            db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.add_person(
+                "alice".to_owned(),
+                Person {
+                    name: "Alice".to_owned(),
+                },
+            )
+            .unwrap();
+            db.assign_story(story_id, Some("alice".to_owned()), None)
+                .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id,
-                db,
+            db
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = db_with_assigned_story();
+
+            let page = MyWork {
+                user: Some("alice".to_owned()),
+                db: Rc::clone(&db),
             };
-            assert_eq!(page.handle_input("").is_ok(), true);
+            assert!(page.draw_page().is_ok());
+
+            let page = MyWork { user: None, db };
+            assert!(page.draw_page().is_ok());
         }
 
         #[test]
-        fn draw_page_should_throw_error_for_invalid_story_id() {
+        fn handle_input_should_only_navigate_to_assigned_stories() {
+            let page = MyWork {
+                user: Some("alice".to_owned()),
+                db: db_with_assigned_story(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("2").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 1,
+                    story_id: 2
+                })
+            );
+            assert_eq!(page.handle_input("3").unwrap(), None);
+            assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+        }
+    }
+
+    mod upcoming_page {
+        use super::*;
+
+        // An epic due in a week with stories due tomorrow, in a month, overdue but closed and
+        // without a due date.
+        fn db_with_due_work() -> Rc<JiraDatabase> {
             let db = Rc::new(JiraDatabase {
                 database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
             });
+            let today = Local::now().date_naive();
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
                 .unwrap();
-            let _ = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            db.set_epic_due(epic_id, today.checked_add_days(Days::new(7)))
                 .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id: 999,
+            for due in [
+                today.succ_opt(),
+                today.checked_add_days(Days::new(30)),
+                today.pred_opt(),
+                None,
+            ] {
+                let story_id = db
+                    .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+                db.set_story_due(story_id, due).unwrap();
+            }
+            db.update_story_status(4, Status::CLOSED).unwrap();
+
+            db
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let page = Upcoming {
+                days: UPCOMING_DAYS,
+                db: db_with_due_work(),
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn due_items_should_list_unfinished_work_soonest_first() {
+            let page = Upcoming {
+                days: UPCOMING_DAYS,
+                db: db_with_due_work(),
+            };
+            let today = Local::now().date_naive();
+
+            let ids = page
+                .due_items(today)
+                .unwrap()
+                .iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![2, 1]);
+
+            let page = Upcoming {
+                days: 60,
+                db: page.db,
+            };
+            assert_eq!(page.due_items(today).unwrap().len(), 3);
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let page = Upcoming {
+                days: UPCOMING_DAYS,
+                db: db_with_due_work(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("30d").unwrap(),
+                Some(Action::NavigateToUpcoming { days: 30 })
+            );
+            assert_eq!(
+                page.handle_input("1").unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("2").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 1,
+                    story_id: 2
+                })
+            );
+            // due after the window
+            assert_eq!(page.handle_input("3").unwrap(), None);
+            assert_eq!(page.handle_input("xd").unwrap(), None);
+        }
+    }
+
+    mod kanban_page {
+        use super::*;
+        use std::collections::BTreeMap;
+
+        // Todo -> Doing -> Done, where Done can't go back.
+        fn db_with_board() -> Rc<JiraDatabase> {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+            let transitions = BTreeMap::from([
+                ("Todo".to_owned(), vec!["Doing".to_owned()]),
+                (
+                    "Doing".to_owned(),
+                    vec!["Todo".to_owned(), "Done".to_owned()],
+                ),
+            ]);
+            db.set_workflow(
+                Workflow::new(&["Todo", "Doing", "Done"], &["Done"], Some(&transitions)).unwrap(),
+            )
+            .unwrap();
+
+            for _ in 0..2 {
+                let epic_id = db
+                    .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                    .unwrap();
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+            }
+            db.update_story_status(2, Status::new("Doing")).unwrap();
+            db.update_story_status(2, Status::new("Done")).unwrap();
+
+            db
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = db_with_board();
+
+            let page = Kanban {
+                epic_id: None,
+                db: Rc::clone(&db),
+            };
+            assert!(page.draw_page().is_ok());
+
+            let page = Kanban {
+                epic_id: Some(1),
                 db,
             };
-            assert_eq!(page.draw_page().is_err(), true);
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_move_stories_along_the_workflow() {
+            let page = Kanban {
+                epic_id: None,
+                db: db_with_board(),
+            };
+
+            assert_eq!(
+                page.handle_input(">4").unwrap(),
+                Some(Action::UpdateStoryStatus {
+                    story_id: 4,
+                    to: Some(Status::new("Doing"))
+                })
+            );
+            // already in the first column
+            assert!(page.handle_input("<4").is_err());
+            // the workflow doesn't allow leaving Done
+            assert!(page.handle_input("<2").is_err());
+            assert!(page.handle_input(">2").is_err());
+            assert_eq!(
+                page.handle_input("4").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 3,
+                    story_id: 4
+                })
+            );
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+
+            // only the stories of the epic are on its board
+            let page = Kanban {
+                epic_id: Some(1),
+                db: page.db,
+            };
+            assert_eq!(page.handle_input(">4").unwrap(), None);
+            assert_eq!(page.handle_input("4").unwrap(), None);
+        }
+    }
+
+    mod search_page {
+        use super::*;
+
+        fn db_with_searchable_work() -> Rc<JiraDatabase> {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new(
+                    "Accounts".to_owned(),
+                    "Sign up and login".to_owned(),
+                ))
+                .unwrap();
+            for (name, description) in [
+                ("Login", ""),
+                ("Password reset", "Link on the login page"),
+                ("Catalog", "Browse products"),
+                ("Login page", "Remember me on the login page"),
+            ] {
+                db.create_story(Story::new(name.to_owned(), description.to_owned()), epic_id)
+                    .unwrap();
+            }
+
+            db
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let page = Search {
+                query: "login".to_owned(),
+                db: db_with_searchable_work(),
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn results_should_rank_name_matches_first() {
+            let page = Search {
+                query: "login".to_owned(),
+                db: db_with_searchable_work(),
+            };
+
+            let results = page.results().unwrap();
+            let ids = results.iter().map(|result| result.id).collect::<Vec<_>>();
+            // the exact name first, `Catalog` only contains `log`
+            assert_eq!(ids, vec![2, 5, 1, 3]);
+            assert_eq!(results[1].snippet, "Remember me on the *login* page");
+            assert_eq!(results[2].snippet, "Sign up and *login*");
+
+            // every word has to match
+            let page = Search {
+                query: "login remember".to_owned(),
+                db: page.db,
+            };
+            let ids = page
+                .results()
+                .unwrap()
+                .iter()
+                .map(|result| result.id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![5]);
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let page = Search {
+                query: "login".to_owned(),
+                db: db_with_searchable_work(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("1").unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("3").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 1,
+                    story_id: 3
+                })
+            );
+            // not a match
+            assert_eq!(page.handle_input("4").unwrap(), None);
+            assert_eq!(page.handle_input("/login").unwrap(), None);
+        }
+    }
+
+    mod sprint_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error_without_an_active_sprint() {
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = SprintPage { db };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error_for_unknown_statuses() {
+            let mock_db = MockDB::new();
+            let today = Local::now().date_naive();
+            let mut db_state = mock_db.read_db().unwrap();
+            let mut sprint = crate::models::Sprint::new("Sprint 1".to_owned(), today, today);
+            for (story_id, status) in [
+                (2, Status::OPEN),
+                (3, Status::new("Gone")),
+                (4, Status::CLOSED),
+            ] {
+                let mut story = Story::new("".to_owned(), "".to_owned());
+                story.status = status;
+                db_state.stories.insert(story_id, story);
+                sprint.stories.push(story_id);
+            }
+            // a story missing from the board is skipped too
+            sprint.stories.push(999);
+            db_state.last_item_id = 4;
+            db_state.sprints.insert(1, sprint);
+            mock_db.write_db(&db_state).unwrap();
+
+            let db = Rc::new(JiraDatabase {
+                database: Box::new(mock_db),
+                actor: None,
+                recorded: Default::default(),
+            });
+
+            let page = SprintPage { db };
+            assert!(page.draw_page().is_ok());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
             let db = Rc::new(JiraDatabase {
                 database: Box::new(MockDB::new()),
+                actor: None,
+                recorded: Default::default(),
             });
+            let page = SprintPage { db: Rc::clone(&db) };
+            assert_eq!(page.handle_input("a").unwrap(), None);
 
+            let today = Local::now().date_naive();
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
                 .unwrap();
             let story_id = db
                 .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
-
-            let page = StoryDetail {
-                epic_id,
-                story_id,
-                db,
-            };
-
-            let p = "p";
-            let u = "u";
-            let d = "d";
-            let some_number = "1";
-            let junk_input = "j983f2j";
-            let junk_input_with_valid_prefix = "p983f2j";
-            let input_with_trailing_white_spaces = "p\n";
+            let other_story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let (sprint_id, _) = db.start_sprint("Sprint 1", today, today).unwrap();
+            db.add_sprint_story(sprint_id, story_id).unwrap();
 
             assert_eq!(
-                page.handle_input(p).unwrap(),
+                page.handle_input("p").unwrap(),
                 Some(Action::NavigateToPreviousPage)
             );
             assert_eq!(
-                page.handle_input(u).unwrap(),
-                Some(Action::UpdateStoryStatus { story_id })
+                page.handle_input("a").unwrap(),
+                Some(Action::AddSprintStory { sprint_id })
             );
             assert_eq!(
-                page.handle_input(d).unwrap(),
-                Some(Action::DeleteStory { epic_id, story_id })
+                page.handle_input("r").unwrap(),
+                Some(Action::RemoveSprintStory { sprint_id })
             );
-            assert_eq!(page.handle_input(some_number).unwrap(), None);
-            assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
-                page.handle_input(junk_input_with_valid_prefix).unwrap(),
-                None
+                page.handle_input(&story_id.to_string()).unwrap(),
+                Some(Action::NavigateToStoryDetail { epic_id, story_id })
             );
+            // not planned for the sprint
             assert_eq!(
-                page.handle_input(input_with_trailing_white_spaces).unwrap(),
+                page.handle_input(&other_story_id.to_string()).unwrap(),
                 None
             );
         }
     }
+
+    mod board_switcher_page {
+        use super::*;
+        use crate::db::Backend;
+
+        fn board(name: &str) -> Board {
+            Board {
+                name: Some(name.to_owned()),
+                path: format!("/boards/{}.json", name),
+                backend: Backend::Json,
+                workflow: None,
+            }
+        }
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let page = BoardSwitcher {
+                boards: vec![board("work"), board("home")],
+                current_board: Some("home".to_owned()),
+            };
+            assert!(page.draw_page().is_ok());
+
+            let page = BoardSwitcher {
+                boards: vec![],
+                current_board: None,
+            };
+            assert!(page.draw_page().is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let page = BoardSwitcher {
+                boards: vec![board("work"), board("home")],
+                current_board: None,
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("2").unwrap(),
+                Some(Action::SwitchBoard {
+                    name: "home".to_owned()
+                })
+            );
+            assert_eq!(page.handle_input("0").unwrap(), None);
+            assert_eq!(page.handle_input("3").unwrap(), None);
+            assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+        }
+    }
 }
@@ -1,5 +1,14 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
 use ellipse::Ellipse;
 
+use crate::models::{is_overdue, Label, ListView, SortKey, Status, Workflow};
+
+// How many rows a list page shows at once.
+pub const PAGE_SIZE: usize = 10;
+
 pub fn get_column_string(text: &str, width: usize) -> String {
     let len = text.len();
 
@@ -31,6 +40,205 @@ pub fn get_column_string(text: &str, width: usize) -> String {
     }
 }
 
+pub fn format_points(points: Option<u32>) -> String {
+    points.map_or_else(|| "-".to_owned(), |points| points.to_string())
+}
+
+// Checklist progress like `3/5`, or `-` for stories without a checklist.
+pub fn format_progress((done, total): (usize, usize)) -> String {
+    if total == 0 {
+        "-".to_owned()
+    } else {
+        format!("{}/{}", done, total)
+    }
+}
+
+// Overdue dates are flagged with a `!`.
+pub fn format_due(due: Option<NaiveDate>, done: bool, today: NaiveDate) -> String {
+    match due {
+        None => "-".to_owned(),
+        Some(due) if is_overdue(Some(due), done, today) => format!("{} !", due),
+        Some(due) => due.to_string(),
+    }
+}
+
+// Names of the labels referenced by `label_ids`, skipping dangling references.
+pub fn label_names(labels: &HashMap<u32, Label>, label_ids: &[u32]) -> Vec<String> {
+    label_ids
+        .iter()
+        .filter_map(|id| labels.get(id))
+        .map(|label| label.name.clone())
+        .collect()
+}
+
+pub fn format_labels(names: &[String]) -> String {
+    if names.is_empty() {
+        "-".to_owned()
+    } else {
+        names.join(", ")
+    }
+}
+
+pub fn has_label(labels: &HashMap<u32, Label>, label_ids: &[u32], name: &str) -> bool {
+    label_ids
+        .iter()
+        .filter_map(|id| labels.get(id))
+        .any(|label| label.matches(name))
+}
+
+// `/label backend` filters by a label, a bare `/label` clears the filter. `None` means the
+// input isn't a filter command at all.
+pub fn parse_label_filter(input: &str) -> Option<Option<String>> {
+    parse_command(input, "/label")
+}
+
+// The argument of a `/command argument` input, `Some(None)` if it was given without one.
+fn parse_command(input: &str, command: &str) -> Option<Option<String>> {
+    let rest = input.strip_prefix(command)?;
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+
+    let argument = rest.trim();
+    Some((!argument.is_empty()).then(|| argument.to_owned()))
+}
+
+// `n` and `b` page through a list of `page_count` pages, `/sort id|name|status` orders it and
+// `/hide status` and `/show status` filter it, a bare `/sort` or `/show` going back to the
+// usual order or showing everything. `None` means the input isn't a view command at all.
+pub fn parse_view_command(
+    input: &str,
+    view: &ListView,
+    page_count: usize,
+    workflow: &Workflow,
+) -> Option<Result<ListView>> {
+    let mut view = view.clone();
+    match input {
+        "n" => view.page = (view.page + 1).min(page_count.saturating_sub(1)),
+        "b" => view.page = view.page.min(page_count).saturating_sub(1),
+        input => {
+            if let Some(sort) = parse_command(input, "/sort") {
+                view.sort = match sort.as_deref().map(SortKey::parse).transpose() {
+                    Ok(sort) => sort,
+                    Err(error) => return Some(Err(error)),
+                };
+            } else if let Some(status) = parse_command(input, "/hide") {
+                let Some(status) = status else {
+                    return Some(Err(anyhow!("/hide needs a status!")));
+                };
+                match workflow.find_state(&status) {
+                    Ok(status) if !view.hidden.contains(&status) => view.hidden.push(status),
+                    Ok(_) => {}
+                    Err(error) => return Some(Err(error)),
+                }
+            } else if let Some(status) = parse_command(input, "/show") {
+                match status.as_deref().map(|status| workflow.find_state(status)) {
+                    None => view.hidden.clear(),
+                    Some(Ok(status)) => view.hidden.retain(|hidden| *hidden != status),
+                    Some(Err(error)) => return Some(Err(error)),
+                }
+            } else {
+                return None;
+            }
+            // the rows moved around, so start over from the first page
+            view.page = 0;
+        }
+    }
+    Some(Ok(view))
+}
+
+// The rows on the `page`th page, the last page if it's past the end, along with the page
+// shown and how many pages there are. An empty list still has one page.
+pub fn paginate<T>(rows: &[T], page: usize) -> (&[T], usize, usize) {
+    let page_count = rows.len().div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(page_count - 1);
+    let end = (page * PAGE_SIZE + PAGE_SIZE).min(rows.len());
+    (&rows[page * PAGE_SIZE..end], page, page_count)
+}
+
+// Splits the terminal between `count` columns separated by ` | `, keeping them readable on
+// narrow terminals at the cost of wrapping.
+pub fn column_width(terminal_width: usize, count: usize) -> usize {
+    const MIN_WIDTH: usize = 12;
+    let separators = count.saturating_sub(1) * " | ".len();
+    (terminal_width.saturating_sub(separators) / count.max(1)).max(MIN_WIDTH)
+}
+
+// Like `page 1/3 | sorted by name | hiding CLOSED`.
+pub fn format_view(view: &ListView, page: usize, page_count: usize) -> String {
+    let mut parts = vec![format!("page {}/{}", page + 1, page_count)];
+    if let Some(sort) = view.sort {
+        parts.push(format!("sorted by {}", sort));
+    }
+    if !view.hidden.is_empty() {
+        parts.push(format!(
+            "hiding {}",
+            view.hidden
+                .iter()
+                .map(Status::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    parts.join(" | ")
+}
+
+// Where `term` occurs in `text` ignoring case, as a char index, preferring a match at the start
+// of a word. The flag tells whether the match starts a word.
+pub fn find_term(text: &str, term: &str) -> Option<(usize, bool)> {
+    let text = fold_case(text);
+    let term = fold_case(term);
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+
+    let mut matches = text
+        .windows(term.len())
+        .enumerate()
+        .filter(|(_, window)| *window == term.as_slice())
+        .map(|(index, _)| (index, index == 0 || !text[index - 1].is_alphanumeric()));
+    let first = matches.next()?;
+    if first.1 {
+        return Some(first);
+    }
+    Some(matches.find(|(_, word_start)| *word_start).unwrap_or(first))
+}
+
+fn fold_case(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+// At most `width` chars of `text` around the match of `len` chars at `start`, with the match
+// wrapped in `*` and cut off ends marked with `...`.
+pub fn match_snippet(text: &str, start: usize, len: usize, width: usize) -> String {
+    let chars = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect::<Vec<_>>();
+    let end = (start + len).min(chars.len());
+    // the context is split evenly around the match, unless one side runs out of text
+    let context = width.saturating_sub(len + "**......".len());
+    let before = start.min((context / 2).max(context.saturating_sub(chars.len() - end)));
+    let from = start - before;
+    let to = (end + context - before).min(chars.len());
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push_str("...");
+    }
+    snippet.extend(&chars[from..start]);
+    snippet.push('*');
+    snippet.extend(&chars[start..end]);
+    snippet.push('*');
+    snippet.extend(&chars[end..to]);
+    if to < chars.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +277,128 @@ mod tests {
         assert_eq!(get_column_string(text3, width), "testme".to_owned());
         assert_eq!(get_column_string(text4, width), "tes...".to_owned());
     }
+
+    #[test]
+    fn format_progress_should_count_done_items() {
+        assert_eq!(format_progress((0, 0)), "-");
+        assert_eq!(format_progress((3, 5)), "3/5");
+    }
+
+    #[test]
+    fn format_due_should_flag_overdue_dates() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 30).unwrap();
+        let yesterday = today.pred_opt();
+
+        assert_eq!(format_due(None, false, today), "-");
+        assert_eq!(format_due(Some(today), false, today), "2026-10-30");
+        assert_eq!(format_due(yesterday, false, today), "2026-10-29 !");
+        assert_eq!(format_due(yesterday, true, today), "2026-10-29");
+    }
+
+    #[test]
+    fn find_term_should_prefer_word_starts() {
+        assert_eq!(find_term("Login page", "LOG"), Some((0, true)));
+        assert_eq!(find_term("Catalog of logs", "log"), Some((11, true)));
+        assert_eq!(find_term("Catalog", "log"), Some((4, false)));
+        assert_eq!(find_term("Catalog", "logs"), None);
+        assert_eq!(find_term("Catalog", ""), None);
+    }
+
+    #[test]
+    fn match_snippet_should_mark_the_match() {
+        assert_eq!(match_snippet("fix the login", 8, 5, 40), "fix the *login*");
+        assert_eq!(
+            match_snippet("a long\ndescription about the login page", 29, 5, 21),
+            "...the *login* pag..."
+        );
+    }
+
+    #[test]
+    fn parse_view_command_should_page_sort_and_filter() {
+        let workflow = Workflow::default();
+        let view = ListView {
+            page: 1,
+            ..ListView::default()
+        };
+
+        let next = parse_view_command("n", &view, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.page, 2);
+        let last = parse_view_command("n", &next, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.page, 2);
+        let back = parse_view_command("b", &view, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(back.page, 0);
+
+        let sorted = parse_view_command("/sort Name", &view, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sorted.sort, Some(SortKey::Name));
+        assert_eq!(sorted.page, 0);
+        assert!(parse_view_command("/sort points", &view, 3, &workflow)
+            .unwrap()
+            .is_err());
+
+        let hidden = parse_view_command("/hide closed", &view, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        let hidden = parse_view_command("/hide resolved", &hidden, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hidden.hidden, vec![Status::CLOSED, Status::RESOLVED]);
+        let shown = parse_view_command("/show closed", &hidden, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert_eq!(shown.hidden, vec![Status::RESOLVED]);
+        let shown = parse_view_command("/show", &shown, 3, &workflow)
+            .unwrap()
+            .unwrap();
+        assert!(shown.hidden.is_empty());
+        assert!(parse_view_command("/hide done", &view, 3, &workflow)
+            .unwrap()
+            .is_err());
+        assert!(parse_view_command("/hide", &view, 3, &workflow)
+            .unwrap()
+            .is_err());
+
+        assert!(parse_view_command("/sorted", &view, 3, &workflow).is_none());
+        assert!(parse_view_command("q", &view, 3, &workflow).is_none());
+    }
+
+    #[test]
+    fn column_width_should_share_the_terminal() {
+        assert_eq!(column_width(80, 4), 17);
+        assert_eq!(column_width(120, 1), 120);
+        assert_eq!(column_width(40, 6), 12);
+    }
+
+    #[test]
+    fn paginate_should_clamp_to_the_last_page() {
+        let rows = (0..25).collect::<Vec<_>>();
+
+        assert_eq!(paginate(&rows, 0), (&rows[0..10], 0, 3));
+        assert_eq!(paginate(&rows, 2), (&rows[20..25], 2, 3));
+        assert_eq!(paginate(&rows, 7), (&rows[20..25], 2, 3));
+        assert_eq!(paginate::<u32>(&[], 1), (&[][..], 0, 1));
+    }
+
+    #[test]
+    fn parse_label_filter_should_set_and_clear_filters() {
+        assert_eq!(
+            parse_label_filter("/label backend"),
+            Some(Some("backend".to_owned()))
+        );
+        assert_eq!(
+            parse_label_filter("/label  needs review "),
+            Some(Some("needs review".to_owned()))
+        );
+        assert_eq!(parse_label_filter("/label"), Some(None));
+        assert_eq!(parse_label_filter("/label "), Some(None));
+        assert_eq!(parse_label_filter("/labels"), None);
+        assert_eq!(parse_label_filter("label backend"), None);
+    }
 }
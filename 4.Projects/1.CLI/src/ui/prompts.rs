@@ -1,14 +1,66 @@
+use chrono::{Local, NaiveDate};
+
 use crate::{
-    io_utils::get_user_input,
-    models::{Epic, Status, Story},
+    io_utils::{get_user_input, wait_for_key_press},
+    models::{
+        parse_due_date, ChecklistItem, Comment, Dependency, Epic, Person, Priority, Status, Story,
+    },
 };
 
+// Epics offered to the user to pick from, sorted by id.
+pub type EpicChoices = [(u32, Epic)];
+pub type PickEpicPrompt = Box<dyn Fn(&EpicChoices) -> Option<u32>>;
+
+// Registered team members sorted by username. Picking someone who is not registered yet
+// hands back their details so they can be added to the registry.
+pub type PersonChoices = [(String, Person)];
+pub type PickPersonPrompt = Box<dyn Fn(&PersonChoices) -> Option<(String, Person)>>;
+
+// Label names offered to the user, who may also type a name that isn't listed.
+pub type PickLabelPrompt = Box<dyn Fn(&[String]) -> Option<String>>;
+
+// Picks a comment of a thread by its position, oldest first.
+pub type PickCommentPrompt = Box<dyn Fn(&[Comment]) -> Option<usize>>;
+
+// Picks a checklist item by its position, and for reordering the position to move it to.
+pub type PickChecklistItemPrompt = Box<dyn Fn(&[ChecklistItem]) -> Option<usize>>;
+pub type MoveChecklistItemPrompt = Box<dyn Fn(&[ChecklistItem]) -> Option<(usize, usize)>>;
+
+// Picks one of the current dependencies of a story.
+pub type PickDependencyPrompt = Box<dyn Fn(&[Dependency]) -> Option<Dependency>>;
+
+// Offers the states the workflow allows moving to from the current one.
+pub type PickStatusPrompt = Box<dyn Fn(&[Status]) -> Option<Status>>;
+
+// Shows the unfinished blockers of a story and asks whether to start it anyway.
+pub type StartBlockedStoryPrompt = Box<dyn Fn(&[(u32, Story)]) -> bool>;
+
 pub struct Prompts {
     pub create_epic: Box<dyn Fn() -> Epic>,
     pub create_story: Box<dyn Fn() -> Story>,
     pub delete_epic: Box<dyn Fn() -> bool>,
     pub delete_story: Box<dyn Fn() -> bool>,
-    pub update_status: Box<dyn Fn() -> Option<Status>>,
+    pub update_status: PickStatusPrompt,
+    pub update_epic: Box<dyn Fn(&Epic) -> Epic>,
+    pub update_story: Box<dyn Fn(&Story) -> Story>,
+    pub update_estimate: Box<dyn Fn(&Story) -> Story>,
+    pub update_due: Box<dyn Fn(Option<NaiveDate>) -> Option<NaiveDate>>,
+    pub move_story: PickEpicPrompt,
+    pub assign: PickPersonPrompt,
+    pub add_label: PickLabelPrompt,
+    pub remove_label: PickLabelPrompt,
+    pub add_comment: Box<dyn Fn() -> Option<String>>,
+    pub delete_comment: PickCommentPrompt,
+    pub add_checklist_item: Box<dyn Fn() -> Option<String>>,
+    pub toggle_checklist_item: PickChecklistItemPrompt,
+    pub remove_checklist_item: PickChecklistItemPrompt,
+    pub move_checklist_item: MoveChecklistItemPrompt,
+    pub add_dependency: Box<dyn Fn() -> Option<Dependency>>,
+    pub remove_dependency: PickDependencyPrompt,
+    pub start_blocked_story: StartBlockedStoryPrompt,
+    pub add_sprint_story: Box<dyn Fn() -> Option<u32>>,
+    pub remove_sprint_story: Box<dyn Fn() -> Option<u32>>,
+    pub search: Box<dyn Fn() -> Option<String>>,
 }
 
 impl Prompts {
@@ -19,6 +71,32 @@ impl Prompts {
             delete_epic: Box::new(delete_epic_prompt),
             delete_story: Box::new(delete_story_prompt),
             update_status: Box::new(update_status_prompt),
+            update_epic: Box::new(update_epic_prompt),
+            update_story: Box::new(update_story_prompt),
+            update_estimate: Box::new(update_estimate_prompt),
+            update_due: Box::new(update_due_prompt),
+            move_story: Box::new(move_story_prompt),
+            assign: Box::new(assign_prompt),
+            add_label: Box::new(add_label_prompt),
+            remove_label: Box::new(remove_label_prompt),
+            add_comment: Box::new(add_comment_prompt),
+            delete_comment: Box::new(delete_comment_prompt),
+            add_checklist_item: Box::new(add_checklist_item_prompt),
+            toggle_checklist_item: Box::new(|checklist| {
+                pick_checklist_item_prompt(checklist, "Toggle item #: ")
+            }),
+            remove_checklist_item: Box::new(|checklist| {
+                pick_checklist_item_prompt(checklist, "Remove item #: ")
+            }),
+            move_checklist_item: Box::new(move_checklist_item_prompt),
+            add_dependency: Box::new(add_dependency_prompt),
+            remove_dependency: Box::new(remove_dependency_prompt),
+            start_blocked_story: Box::new(start_blocked_story_prompt),
+            add_sprint_story: Box::new(|| story_id_prompt("Story id to add to the sprint: ")),
+            remove_sprint_story: Box::new(|| {
+                story_id_prompt("Story id to remove from the sprint: ")
+            }),
+            search: Box::new(search_prompt),
         }
     }
 }
@@ -55,6 +133,315 @@ fn create_story_prompt() -> Story {
     story
 }
 
+// Shows the current value and keeps it when the user just presses enter.
+fn prompt_with_default(label: &str, current: &str) -> String {
+    println!("{} [{}]: ", label, current);
+
+    let input = get_user_input();
+
+    if input.trim().is_empty() {
+        current.to_owned()
+    } else {
+        input.trim().to_owned()
+    }
+}
+
+fn update_epic_prompt(epic: &Epic) -> Epic {
+    println!("----------------------------");
+
+    let mut epic = epic.clone();
+    epic.name = prompt_with_default("Epic Name", &epic.name);
+    epic.description = prompt_with_default("Epic Description", &epic.description);
+
+    epic
+}
+
+fn update_story_prompt(story: &Story) -> Story {
+    println!("----------------------------");
+
+    let mut story = story.clone();
+    story.name = prompt_with_default("Story Name", &story.name);
+    story.description = prompt_with_default("Story Description", &story.description);
+
+    story
+}
+
+fn update_estimate_prompt(story: &Story) -> Story {
+    println!("----------------------------");
+
+    let mut story = story.clone();
+
+    println!(
+        "Priority (1 - CRITICAL, 2 - HIGH, 3 - MEDIUM, 4 - LOW) [{}]: ",
+        story.priority
+    );
+
+    story.priority = match get_user_input().trim() {
+        "1" => Priority::Critical,
+        "2" => Priority::High,
+        "3" => Priority::Medium,
+        "4" => Priority::Low,
+        _ => story.priority,
+    };
+
+    let current_points = story
+        .points
+        .map_or_else(|| "-".to_owned(), |points| points.to_string());
+    println!("Story Points (- to clear) [{}]: ", current_points);
+
+    let points = get_user_input();
+    match points.trim() {
+        "-" => story.points = None,
+        points => {
+            if let Ok(points) = points.parse::<u32>() {
+                story.points = Some(points);
+            }
+        }
+    }
+
+    story
+}
+
+// Keeps the current due date when the input is empty or can't be read.
+fn update_due_prompt(due: Option<NaiveDate>) -> Option<NaiveDate> {
+    println!("----------------------------");
+
+    let current_due = due.map_or_else(|| "-".to_owned(), |due| due.to_string());
+    println!(
+        "Due date (YYYY-MM-DD, +Nd for N days from today, - to clear) [{}]: ",
+        current_due
+    );
+
+    match get_user_input().trim() {
+        "" => due,
+        "-" => None,
+        input => match parse_due_date(input, Local::now().date_naive()) {
+            Ok(new_due) => Some(new_due),
+            Err(error) => {
+                println!(
+                    "{}, keeping {}\nPress any key to continue...",
+                    error, current_due
+                );
+                wait_for_key_press();
+                due
+            }
+        },
+    }
+}
+
+fn move_story_prompt(target_epics: &EpicChoices) -> Option<u32> {
+    println!("----------------------------");
+
+    for (epic_id, epic) in target_epics {
+        println!("{} - {}", epic_id, epic.name);
+    }
+
+    println!("Move story to epic: ");
+
+    let epic_id = get_user_input().trim().parse::<u32>().ok()?;
+
+    target_epics
+        .iter()
+        .any(|(id, _)| *id == epic_id)
+        .then_some(epic_id)
+}
+
+fn assign_prompt(people: &PersonChoices) -> Option<(String, Person)> {
+    println!("----------------------------");
+
+    for (username, person) in people {
+        println!("{} - {}", username, person.name);
+    }
+
+    println!("Assign to (username, a new one registers a team member): ");
+
+    let username = get_user_input().trim().to_owned();
+    if username.is_empty() {
+        return None;
+    }
+
+    if let Some((_, person)) = people.iter().find(|(known, _)| *known == username) {
+        return Some((username, person.clone()));
+    }
+
+    println!("Full name of {}: ", username);
+
+    let name = get_user_input().trim().to_owned();
+
+    Some((username, Person { name }))
+}
+
+fn add_label_prompt(board_labels: &[String]) -> Option<String> {
+    println!("----------------------------");
+
+    if !board_labels.is_empty() {
+        println!("Labels on this board: {}", board_labels.join(", "));
+    }
+
+    println!("Add label: ");
+
+    let label = get_user_input();
+    let label = label.trim();
+
+    (!label.is_empty()).then(|| label.to_owned())
+}
+
+fn remove_label_prompt(item_labels: &[String]) -> Option<String> {
+    println!("----------------------------");
+
+    println!("Current labels: {}", item_labels.join(", "));
+    println!("Remove label: ");
+
+    let label = get_user_input();
+    let label = label.trim();
+
+    (!label.is_empty()).then(|| label.to_owned())
+}
+
+fn add_comment_prompt() -> Option<String> {
+    println!("----------------------------");
+
+    println!("Comment (leave empty to cancel): ");
+
+    let body = get_user_input();
+    let body = body.trim();
+
+    (!body.is_empty()).then(|| body.to_owned())
+}
+
+// Returns the position of the picked comment in the thread.
+fn delete_comment_prompt(comments: &[Comment]) -> Option<usize> {
+    println!("----------------------------");
+
+    for (index, comment) in comments.iter().enumerate() {
+        println!("{} - {}", index + 1, comment);
+    }
+
+    println!("Delete comment #: ");
+
+    let number = get_user_input().trim().parse::<usize>().ok()?;
+    number
+        .checked_sub(1)
+        .filter(|index| *index < comments.len())
+}
+
+fn add_checklist_item_prompt() -> Option<String> {
+    println!("----------------------------");
+
+    println!("Checklist item (leave empty to cancel): ");
+
+    let text = get_user_input();
+    let text = text.trim();
+
+    (!text.is_empty()).then(|| text.to_owned())
+}
+
+// Reads a 1-based item number and returns the matching position in the checklist.
+fn read_checklist_position(checklist: &[ChecklistItem]) -> Option<usize> {
+    let number = get_user_input().trim().parse::<usize>().ok()?;
+    number
+        .checked_sub(1)
+        .filter(|index| *index < checklist.len())
+}
+
+fn print_checklist(checklist: &[ChecklistItem]) {
+    println!("----------------------------");
+
+    for (index, item) in checklist.iter().enumerate() {
+        println!("{} - {}", index + 1, item);
+    }
+}
+
+fn pick_checklist_item_prompt(checklist: &[ChecklistItem], question: &str) -> Option<usize> {
+    print_checklist(checklist);
+
+    println!("{}", question);
+
+    read_checklist_position(checklist)
+}
+
+fn move_checklist_item_prompt(checklist: &[ChecklistItem]) -> Option<(usize, usize)> {
+    print_checklist(checklist);
+
+    println!("Move item #: ");
+    let from = read_checklist_position(checklist)?;
+
+    println!("To position #: ");
+    let to = read_checklist_position(checklist)?;
+
+    Some((from, to))
+}
+
+fn add_dependency_prompt() -> Option<Dependency> {
+    println!("----------------------------");
+
+    println!("This story (1 - IS BLOCKED BY, 2 - BLOCKS) another story: ");
+
+    let blocked = match get_user_input().trim() {
+        "1" => true,
+        "2" => false,
+        _ => return None,
+    };
+
+    println!("Other story id: ");
+
+    let story_id = get_user_input().trim().parse::<u32>().ok()?;
+
+    Some(if blocked {
+        Dependency::BlockedBy(story_id)
+    } else {
+        Dependency::Blocks(story_id)
+    })
+}
+
+fn search_prompt() -> Option<String> {
+    println!("----------------------------");
+
+    println!("Search epics and stories (leave empty to cancel): ");
+
+    let query = get_user_input();
+    let query = query.trim();
+
+    (!query.is_empty()).then(|| query.to_owned())
+}
+
+fn story_id_prompt(message: &str) -> Option<u32> {
+    println!("----------------------------");
+
+    println!("{}", message);
+
+    get_user_input().trim().parse::<u32>().ok()
+}
+
+fn remove_dependency_prompt(dependencies: &[Dependency]) -> Option<Dependency> {
+    println!("----------------------------");
+
+    for (index, dependency) in dependencies.iter().enumerate() {
+        println!("{} - {}", index + 1, dependency);
+    }
+
+    println!("Remove dependency #: ");
+
+    let number = get_user_input().trim().parse::<usize>().ok()?;
+    number
+        .checked_sub(1)
+        .and_then(|index| dependencies.get(index))
+        .copied()
+}
+
+fn start_blocked_story_prompt(blockers: &[(u32, Story)]) -> bool {
+    println!("----------------------------");
+
+    println!("Warning: this story is still blocked by:");
+    for (story_id, story) in blockers {
+        println!("{} - {} ({})", story_id, story.name, story.status);
+    }
+
+    println!("Start it anyway? [Y/n]: ");
+
+    get_user_input().trim().eq("Y")
+}
+
 fn delete_epic_prompt() -> bool {
     println!("----------------------------");
 
@@ -83,32 +470,18 @@ fn delete_story_prompt() -> bool {
     false
 }
 
-fn update_status_prompt() -> Option<Status> {
+fn update_status_prompt(next_states: &[Status]) -> Option<Status> {
     println!("----------------------------");
 
-    println!("New Status (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED): ");
+    let choices = next_states
+        .iter()
+        .enumerate()
+        .map(|(index, status)| format!("{} - {}", index + 1, status))
+        .collect::<Vec<_>>();
+    println!("New Status ({}): ", choices.join(", "));
 
     let status = get_user_input();
 
-    let status = status.trim().parse::<u8>();
-
-    if let Ok(status) = status {
-        match status {
-            1 => {
-                return Some(Status::Open);
-            }
-            2 => {
-                return Some(Status::InProgress);
-            }
-            3 => {
-                return Some(Status::Resolved);
-            }
-            4 => {
-                return Some(Status::Closed);
-            }
-            _ => return None,
-        }
-    }
-
-    None
+    let index = status.trim().parse::<usize>().ok()?;
+    next_states.get(index.checked_sub(1)?).cloned()
 }